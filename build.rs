@@ -1,3 +1,8 @@
+#[path = "src/comments.rs"]
+mod comments;
+#[path = "src/duplicates.rs"]
+mod duplicates;
+
 use serde_json::{self, Value};
 use std::{
     env,
@@ -13,6 +18,10 @@ fn main() -> std::io::Result<()> {
 
     fs::create_dir_all(&dest_dir)?;
 
+    println!("cargo:rerun-if-env-changed=BUDOUX_VERBOSE_BUILD");
+    let verbose = env::var("BUDOUX_VERBOSE_BUILD").is_ok_and(|v| v == "1");
+    let mut sizes = Vec::new();
+
     let features = vec![
         ("ja", env::var("CARGO_FEATURE_JA").is_ok()),
         ("zh-hans", env::var("CARGO_FEATURE_ZH_HANS").is_ok()),
@@ -27,28 +36,60 @@ fn main() -> std::io::Result<()> {
 
         let model_path = models_dir.join(format!("{}.json", lang));
         let contents = fs::read_to_string(&model_path)?;
-        let model = serde_json::from_str::<Value>(&contents)?;
+        let json = comments::strip_json_comments(&contents);
+        let model = serde_json::from_str::<Value>(&json)?;
+        for (group, key) in duplicates::duplicate_keys(&json) {
+            match key {
+                Some(key) => println!(
+                    "cargo:warning={}: duplicate key `{}` in `{}`",
+                    model_path.display(),
+                    key,
+                    group
+                ),
+                None => println!(
+                    "cargo:warning={}: duplicate feature group `{}`",
+                    model_path.display(),
+                    group
+                ),
+            }
+        }
 
         let hashmap = generate_rust_hashmap(&model);
+        let name = sanitize_var_name(lang).to_ascii_uppercase();
         let rust_code = format!(
-            r#"pub static {}_MODEL: std::sync::LazyLock<Model> = std::sync::LazyLock::new(|| {{
-{}
-}});"#,
-            sanitize_var_name(lang).to_ascii_uppercase(),
-            hashmap
+            r#"#[allow(clippy::all, clippy::pedantic, unused)]
+pub static {name}_MODEL: std::sync::LazyLock<Model> = std::sync::LazyLock::new(|| {{
+#[cfg(feature = "tracing")]
+let _span = tracing::debug_span!("model_init", lang = "{lang}").entered();
+{hashmap}
+}});"#
         );
 
         let model_name = lang.replace("-", "_");
         let dest_path = dest_dir.join(format!("{}.rs", model_name));
+        let first_build = !dest_path.exists();
         let mut file = File::create(&dest_path)?;
         file.write_all(rust_code.as_bytes())?;
 
+        if first_build || verbose {
+            sizes.push((dest_path, rust_code.len()));
+        }
+
         println!("cargo:rerun-if-changed={}", model_path.display());
     }
 
+    if !sizes.is_empty() {
+        for (path, size) in &sizes {
+            println!("cargo:warning=generated {} ({} bytes)", path.display(), size);
+        }
+        let total = sizes.iter().map(|(_, size)| size).sum::<usize>();
+        println!("cargo:warning=generated models total: {} bytes", total);
+    }
+
     Ok(())
 }
 
+#[allow(clippy::collapsible_if)]
 fn generate_rust_hashmap(json_data: &Value) -> String {
     let mut code = String::from("let mut model = HashMap::new();\n");
 
@@ -79,7 +120,7 @@ fn generate_rust_hashmap(json_data: &Value) -> String {
         }
     }
 
-    code.push_str("model");
+    code.push_str("Model::from(model)");
     code
 }
 
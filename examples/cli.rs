@@ -25,16 +25,17 @@ enum Language {
 
 fn main() {
     let args = Cli::parse();
-    let parser = match args.lang {
+    let language = match args.lang {
         #[cfg(feature = "ja")]
-        Language::Japanese => budoux_rs::Parser::load_default_japanese_parser(),
+        Language::Japanese => budoux_rs::Language::Japanese,
         #[cfg(feature = "zh-hans")]
-        Language::SimplifiedChinese => budoux_rs::Parser::load_default_simplified_chinese_parser(),
+        Language::SimplifiedChinese => budoux_rs::Language::SimplifiedChinese,
         #[cfg(feature = "zh-hant")]
-        Language::TraditionalChinese => budoux_rs::Parser::load_default_traditional_chinese_parser(),
+        Language::TraditionalChinese => budoux_rs::Language::TraditionalChinese,
         #[cfg(feature = "th")]
-        Language::Thai => budoux_rs::Parser::load_default_thai_parser(),
+        Language::Thai => budoux_rs::Language::Thai,
     };
+    let parser = language.load_parser();
 
     let text = args.text.trim();
     let result = parser.parse(text);
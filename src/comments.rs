@@ -0,0 +1,79 @@
+//! Comment stripping for JSON model files, shared with `build.rs`.
+
+/// Removes `//` line comments and `/* */` block comments outside of string literals, so that
+/// lightly annotated model files still parse as JSON. Line breaks are kept to preserve the line
+/// numbers in parse errors.
+pub fn strip_json_comments(json: &str) -> String {
+    let mut output = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+            match c {
+                '\\' => output.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                output.push(c);
+            }
+            ('/', Some('/')) => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        output.push(c);
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = None;
+                for c in chars.by_ref() {
+                    if previous == Some('*') && c == '/' {
+                        break;
+                    }
+                    if c == '\n' {
+                        output.push(c);
+                    }
+                    previous = Some(c);
+                }
+            }
+            _ => output.push(c),
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_strip_comments_keeping_line_breaks() {
+        let json = "{\n  // a note\n  \"UW4\": /* inline */ {\"a\": 1} /* two\nlines */\n}";
+
+        assert_eq!(strip_json_comments(json), "{\n  \n  \"UW4\":  {\"a\": 1} \n\n}");
+    }
+
+    #[test]
+    fn should_keep_comment_markers_inside_strings() {
+        assert_eq!(strip_json_comments(r#"{"/*": "//"}"#), r#"{"/*": "//"}"#);
+        assert_eq!(strip_json_comments(r#"{"a\"/*": 1}"#), r#"{"a\"/*": 1}"#);
+        assert_eq!(strip_json_comments(r#"{"a\\": 1} // b"#), r#"{"a\\": 1} "#);
+    }
+
+    #[test]
+    fn should_strip_comments_running_to_the_end() {
+        assert_eq!(strip_json_comments("{} //"), "{} ");
+        assert_eq!(strip_json_comments("{} // note"), "{} ");
+        assert_eq!(strip_json_comments("{} /* note\n*"), "{} \n");
+    }
+}
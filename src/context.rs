@@ -0,0 +1,69 @@
+/// A sentence with its character offsets cached, for scoring many positions of it without
+/// walking the sentence again for every feature window.
+///
+/// Created by `Parser::score_with_context`.
+#[derive(Debug, Clone)]
+pub struct ScoringContext<'a> {
+    /// Sentence being scored
+    sentence: &'a str,
+    /// Byte offset of every character, followed by the length of the sentence
+    offsets: Vec<usize>,
+}
+
+impl<'a> ScoringContext<'a> {
+    pub(crate) fn new(sentence: &'a str) -> Self {
+        let offsets = sentence
+            .char_indices()
+            .map(|(byte, _)| byte)
+            .chain([sentence.len()])
+            .collect();
+        ScoringContext { sentence, offsets }
+    }
+
+    /// Returns the sentence being scored.
+    pub fn sentence(&self) -> &'a str {
+        self.sentence
+    }
+
+    /// Returns the number of characters in the sentence.
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Checks whether the sentence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the characters `start..end` of the sentence in constant time.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first character position, clamped to the length of the sentence.
+    /// * `end` - The end character position, clamped to the length of the sentence.
+    ///
+    /// # Returns
+    ///
+    /// The substring, which is empty if `start >= end`.
+    pub fn substring(&self, start: usize, end: usize) -> &'a str {
+        let start = self.offsets[start.min(self.len())];
+        let end = self.offsets[end.min(self.len())];
+        self.sentence.get(start..end).unwrap_or("")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_slice_by_character_positions() {
+        let context = ScoringContext::new("あいaう");
+
+        assert_eq!(context.len(), 4);
+        assert_eq!(context.substring(1, 3), "いa");
+        assert_eq!(context.substring(2, 10), "aう");
+        assert_eq!(context.substring(3, 1), "");
+        assert!(ScoringContext::new("").is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use crate::Parser;
+use crate::segmented::parse_segmented;
+use std::{fs, io, path::Path, str::FromStr};
+
+/// A set of reference segmentations for evaluating a parser.
+///
+/// The text format has one entry per line: the original sentence, a tab, and the expected
+/// segmentation with chunks separated by `▁`, e.g. `今日は天気です。\t今日は▁天気です。`.
+/// Empty lines are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Corpus {
+    /// Sentences paired with their expected boundary positions
+    entries: Vec<(String, Vec<usize>)>,
+}
+
+/// Boundary-level precision, recall and F1 score of a parser against a corpus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalResult {
+    /// Fraction of predicted boundaries that are expected
+    pub precision: f64,
+    /// Fraction of expected boundaries that are predicted
+    pub recall: f64,
+    /// Harmonic mean of precision and recall
+    pub f1: f64,
+}
+
+impl Corpus {
+    /// Loads a corpus from a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to a corpus file.
+    ///
+    /// # Returns
+    ///
+    /// The loaded corpus, or an error if the file cannot be read or is malformed.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?.parse()
+    }
+
+    /// Returns the number of sentences in the corpus.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether the corpus contains no sentences.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Parses every sentence of the corpus and compares the boundaries with the expected ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `parser` - The parser to evaluate.
+    ///
+    /// # Returns
+    ///
+    /// The precision, recall and F1 score over all boundaries in the corpus.
+    pub fn evaluate(&self, parser: &Parser<'_>) -> EvalResult {
+        let mut true_positives = 0;
+        let mut predicted = 0;
+        let mut expected = 0;
+
+        for (sentence, boundaries) in &self.entries {
+            let result = parser.char_boundaries(sentence);
+            true_positives += result.iter().filter(|i| boundaries.binary_search(i).is_ok()).count();
+            predicted += result.len();
+            expected += boundaries.len();
+        }
+
+        let ratio = |n: usize, d: usize| if d == 0 { 1.0 } else { n as f64 / d as f64 };
+        let precision = ratio(true_positives, predicted);
+        let recall = ratio(true_positives, expected);
+        let f1 = if precision + recall == 0.0 {
+            0.0
+        } else {
+            2.0 * precision * recall / (precision + recall)
+        };
+
+        EvalResult { precision, recall, f1 }
+    }
+}
+
+impl FromStr for Corpus {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |line: usize, message: &str| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {}", line + 1, message))
+        };
+        let mut entries = Vec::new();
+
+        for (n, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (sentence, segmented) = line
+                .split_once('\t')
+                .ok_or_else(|| invalid(n, "missing tab separator"))?;
+            let (chunks, boundaries) = parse_segmented(segmented);
+            if chunks != sentence {
+                return Err(invalid(n, "expected chunks do not match the sentence"));
+            }
+
+            entries.push((chunks, boundaries));
+        }
+
+        Ok(Corpus { entries })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+
+    #[test]
+    fn should_evaluate_boundaries_against_the_corpus() {
+        let corpus = "abcdeabcd\ta▁bcde▁abcd\n\nxyz\txyz\n".parse::<Corpus>().unwrap();
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+
+        let result = corpus.evaluate(&parser);
+
+        assert_eq!(corpus.len(), 2);
+        assert_eq!(result.precision, 0.5);
+        assert_eq!(result.recall, 0.5);
+        assert_eq!(result.f1, 0.5);
+    }
+
+    #[test]
+    fn should_reject_malformed_lines() {
+        assert!("abc".parse::<Corpus>().is_err());
+        assert!("abc\ta▁bd".parse::<Corpus>().is_err());
+    }
+}
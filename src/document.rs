@@ -0,0 +1,7 @@
+/// A paragraph of a document split into sentences and chunks, as returned by
+/// `Parser::parse_document`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Paragraph<'a> {
+    /// The chunks of every sentence of the paragraph
+    pub sentences: Vec<Vec<&'a str>>,
+}
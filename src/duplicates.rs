@@ -0,0 +1,75 @@
+//! Duplicate key detection for JSON models, shared with `build.rs`.
+
+use std::collections::HashSet;
+
+/// Finds the keys repeated within the same object of a BudouX JSON model, which JSON parsers
+/// silently resolve by keeping the last value.
+///
+/// The text is expected to be valid JSON. Keys are compared as written, so the same key spelled
+/// with different escape sequences is not reported.
+///
+/// # Arguments
+///
+/// * `json` - The JSON text.
+///
+/// # Returns
+///
+/// `(group, None)` for every repeated feature group and `(group, Some(key))` for every repeated
+/// key within a group, in order of appearance.
+pub fn duplicate_keys(json: &str) -> Vec<(String, Option<String>)> {
+    let mut duplicates = Vec::new();
+    // The keys seen so far in each open container, or `None` for an array.
+    let mut seen: Vec<Option<HashSet<String>>> = Vec::new();
+    // The key leading to each open container.
+    let mut path: Vec<String> = Vec::new();
+    let mut last_key = None;
+    let mut expect_key = false;
+    let mut chars = json.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut string = String::new();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            string.push(c);
+                            string.extend(chars.next());
+                        }
+                        '"' => break,
+                        _ => string.push(c),
+                    }
+                }
+
+                if !expect_key {
+                    continue;
+                }
+                expect_key = false;
+                if let Some(Some(keys)) = seen.last_mut()
+                    && !keys.insert(string.clone())
+                {
+                    match seen.len() {
+                        1 => duplicates.push((string.clone(), None)),
+                        2 => duplicates.push((path[1].clone(), Some(string.clone()))),
+                        _ => {}
+                    }
+                }
+                last_key = Some(string);
+            }
+            '{' | '[' => {
+                path.push(last_key.take().unwrap_or_default());
+                seen.push((c == '{').then(HashSet::new));
+                expect_key = c == '{';
+            }
+            '}' | ']' => {
+                seen.pop();
+                path.pop();
+                expect_key = false;
+            }
+            ',' => expect_key = matches!(seen.last(), Some(Some(_))),
+            _ => {}
+        }
+    }
+
+    duplicates
+}
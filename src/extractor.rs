@@ -0,0 +1,48 @@
+/// A source of additional boundary scores on top of the model features.
+///
+/// The parser adds the extra score to the model score at every position, so positive values
+/// favor a boundary and negative values suppress one. Implementors must be thread-safe so that
+/// parsers remain shareable across threads.
+pub trait FeatureExtractor: Send + Sync {
+    /// Computes the extra score for a boundary before the character at `position`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - The sentence being parsed.
+    /// * `position` - The character position being scored.
+    ///
+    /// # Returns
+    ///
+    /// The score to add to the model score.
+    fn extra_score(&self, sentence: &str, position: usize) -> i64;
+}
+
+/// A feature extractor backed by a closure.
+pub struct FnExtractor<F> {
+    /// Function computing the extra score for a sentence and character position
+    f: F,
+}
+
+impl<F> FnExtractor<F>
+where
+    F: Fn(&str, usize) -> i64 + Send + Sync + 'static,
+{
+    /// Constructs a feature extractor from a closure.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function taking the sentence and a character position and returning the extra
+    ///   score for a boundary before that position.
+    pub fn new(f: F) -> Self {
+        FnExtractor { f }
+    }
+}
+
+impl<F> FeatureExtractor for FnExtractor<F>
+where
+    F: Fn(&str, usize) -> i64 + Send + Sync + 'static,
+{
+    fn extra_score(&self, sentence: &str, position: usize) -> i64 {
+        (self.f)(sentence, position)
+    }
+}
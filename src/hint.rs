@@ -0,0 +1,24 @@
+/// Whether a line may break after a piece of text, as reported by
+/// `Parser::parse_with_linebreak_hints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BreakClass {
+    /// A semantic boundary, where a line may break
+    Allowed,
+    /// A hard line break, or the end of the text, where a line must break
+    Mandatory,
+    /// A semantic boundary directly before a hard line break, where a line must not break
+    Prohibited,
+}
+
+/// A piece of text together with the kind of break that follows it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BreakOpportunity<'a> {
+    /// The text of the piece, including a trailing hard line break character
+    pub text: &'a str,
+    /// The kind of break after the piece
+    pub break_class: BreakClass,
+}
+
+/// Characters that force a line break after them: LF, NEL, LINE SEPARATOR and PARAGRAPH
+/// SEPARATOR.
+pub(crate) const MANDATORY_BREAKS: [char; 4] = ['\n', '\u{85}', '\u{2028}', '\u{2029}'];
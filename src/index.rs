@@ -0,0 +1,21 @@
+/// A position in a string counted in characters (Unicode scalar values).
+///
+/// Not usable for slicing the string; see `ByteIndex` for that.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CharIndex(pub usize);
+
+/// A position in a string counted in bytes, usable for slicing it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteIndex(pub usize);
+
+impl From<CharIndex> for usize {
+    fn from(index: CharIndex) -> Self {
+        index.0
+    }
+}
+
+impl From<ByteIndex> for usize {
+    fn from(index: ByteIndex) -> Self {
+        index.0
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{Parser, ScoringContext};
+use std::iter::FusedIterator;
+
+/// A lazy iterator over the boundary positions of a sentence, in characters.
+///
+/// Each position is scored only when the iterator reaches it, so stopping early avoids scoring
+/// the rest of the sentence. Created by `Parser::boundaries`.
+pub struct Boundaries<'a> {
+    /// Parser used for scoring
+    parser: &'a Parser<'a>,
+    /// Sentence being parsed
+    context: ScoringContext<'a>,
+    /// Next character position to score
+    position: usize,
+}
+
+impl<'a> Boundaries<'a> {
+    pub(crate) fn new(parser: &'a Parser<'a>, sentence: &'a str) -> Self {
+        Boundaries {
+            parser,
+            context: ScoringContext::new(sentence),
+            position: 1,
+        }
+    }
+}
+
+impl Iterator for Boundaries<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.position < self.context.len() {
+            let i = self.position;
+            self.position += 1;
+            if self
+                .parser
+                .is_boundary_score(self.parser.score_in_context(&self.context, i))
+            {
+                return Some(i);
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.context.len().saturating_sub(self.position)))
+    }
+}
+
+impl FusedIterator for Boundaries<'_> {}
+
+/// A lazy iterator over the semantic chunks of a sentence.
+///
+/// Created by `Parser::chunks`.
+pub struct ChunkIterator<'a> {
+    /// Remaining boundaries of the sentence
+    boundaries: Boundaries<'a>,
+    /// Sentence being parsed
+    sentence: &'a str,
+    /// Start of the current chunk in characters
+    start_char: usize,
+    /// Start of the current chunk in bytes
+    start_byte: usize,
+    /// Chunks computed upfront, yielded instead of scoring lazily
+    computed: Option<std::vec::IntoIter<&'a str>>,
+}
+
+impl<'a> ChunkIterator<'a> {
+    pub(crate) fn new(parser: &'a Parser<'a>, sentence: &'a str) -> Self {
+        ChunkIterator {
+            boundaries: Boundaries::new(parser, sentence),
+            sentence,
+            start_char: 0,
+            start_byte: 0,
+            computed: None,
+        }
+    }
+
+    pub(crate) fn from_chunks(parser: &'a Parser<'a>, chunks: Vec<&'a str>) -> Self {
+        ChunkIterator {
+            computed: Some(chunks.into_iter()),
+            ..ChunkIterator::new(parser, "")
+        }
+    }
+}
+
+impl<'a> Iterator for ChunkIterator<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if let Some(chunks) = &mut self.computed {
+            return chunks.next();
+        }
+        if self.start_byte >= self.sentence.len() {
+            return None;
+        }
+
+        let rest = &self.sentence[self.start_byte..];
+        let end_byte = match self.boundaries.next() {
+            Some(boundary) => {
+                let offset = rest
+                    .char_indices()
+                    .nth(boundary - self.start_char)
+                    .map(|(byte, _)| byte);
+                self.start_char = boundary;
+                offset.unwrap_or(rest.len())
+            }
+            None => rest.len(),
+        };
+        self.start_byte += end_byte;
+
+        Some(&rest[..end_byte])
+    }
+}
+
+impl FusedIterator for ChunkIterator<'_> {}
+
+/// An iterator over the semantic chunks of a sentence whose boundaries are computed upfront,
+/// so that the number of chunks is known.
+///
+/// Created by `Parser::chunks_exact`.
+pub struct ChunksExact<'a> {
+    /// Sentence being parsed
+    sentence: &'a str,
+    /// Byte offsets of the chunk edges, including the start and end of the sentence
+    offsets: Vec<usize>,
+    /// Index of the next chunk from the front
+    front: usize,
+    /// Index one past the next chunk from the back
+    back: usize,
+}
+
+impl<'a> ChunksExact<'a> {
+    pub(crate) fn new(sentence: &'a str, byte_boundaries: Vec<usize>) -> Self {
+        let mut offsets = Vec::new();
+        if !sentence.is_empty() {
+            offsets.push(0);
+            offsets.extend(byte_boundaries);
+            offsets.push(sentence.len());
+        }
+        let back = offsets.len().saturating_sub(1);
+
+        ChunksExact {
+            sentence,
+            offsets,
+            front: 0,
+            back,
+        }
+    }
+}
+
+impl<'a> Iterator for ChunksExact<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let chunk = &self.sentence[self.offsets[self.front]..self.offsets[self.front + 1]];
+        self.front += 1;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for ChunksExact<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(&self.sentence[self.offsets[self.back]..self.offsets[self.back + 1]])
+    }
+}
+
+impl ExactSizeIterator for ChunksExact<'_> {}
+
+impl FusedIterator for ChunksExact<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::Parser;
+    use crate::model_from_tuples;
+
+    #[test]
+    fn should_yield_boundaries_lazily() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+        let mut boundaries = parser.boundaries("abcdeabcd");
+
+        assert_eq!(boundaries.next(), Some(1));
+        assert_eq!(boundaries.size_hint(), (0, Some(7)));
+        assert_eq!(boundaries.collect::<Vec<_>>(), vec![6]);
+        assert_eq!(parser.boundaries("").next(), None);
+    }
+
+    #[test]
+    fn should_yield_chunks_lazily() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("い", 10000)] });
+
+        assert_eq!(
+            parser.chunks("あいうあいう").collect::<Vec<_>>(),
+            vec!["あ", "いうあ", "いう"]
+        );
+        assert_eq!(parser.chunks("あいうあいう").next(), Some("あ"));
+        assert_eq!(parser.chunks("").next(), None);
+    }
+
+    #[test]
+    fn should_know_the_number_of_chunks_upfront() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("い", 10000)] });
+        let mut chunks = parser.chunks_exact("あいうあいう");
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks.next(), Some("あ"));
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.collect::<Vec<_>>(), vec!["いうあ", "いう"]);
+        assert_eq!(parser.chunks_exact("").len(), 0);
+    }
+
+    #[test]
+    fn should_traverse_chunks_from_both_ends() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("い", 10000)] });
+
+        assert_eq!(
+            parser.chunks_exact("あいうあいう").rev().collect::<Vec<_>>(),
+            vec!["いう", "いうあ", "あ"]
+        );
+
+        let mut chunks = parser.chunks_exact("あいうあいう");
+        assert_eq!(chunks.next_back(), Some("いう"));
+        assert_eq!(chunks.next(), Some("あ"));
+        assert_eq!(chunks.next_back(), Some("いうあ"));
+        assert_eq!(chunks.next(), None);
+        assert_eq!(chunks.next_back(), None);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::Parser;
+use std::{fmt, str::FromStr};
+
+/// A language with a default model compiled into the crate.
+///
+/// Each variant is only available when the feature of its model is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    /// Japanese, enabled by the `ja` feature
+    #[cfg(feature = "ja")]
+    Japanese,
+    /// Simplified Chinese, enabled by the `zh-hans` feature
+    #[cfg(feature = "zh-hans")]
+    SimplifiedChinese,
+    /// Traditional Chinese, enabled by the `zh-hant` feature
+    #[cfg(feature = "zh-hant")]
+    TraditionalChinese,
+    /// Thai, enabled by the `th` feature
+    #[cfg(feature = "th")]
+    Thai,
+}
+
+impl Language {
+    /// Loads a parser equipped with the default model of the language.
+    ///
+    /// # Returns
+    ///
+    /// A parser with the default model of the language.
+    pub fn load_parser(&self) -> Parser<'static> {
+        match *self {
+            #[cfg(feature = "ja")]
+            Language::Japanese => Parser::load_default_japanese_parser(),
+            #[cfg(feature = "zh-hans")]
+            Language::SimplifiedChinese => Parser::load_default_simplified_chinese_parser(),
+            #[cfg(feature = "zh-hant")]
+            Language::TraditionalChinese => Parser::load_default_traditional_chinese_parser(),
+            #[cfg(feature = "th")]
+            Language::Thai => Parser::load_default_thai_parser(),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = LanguageParseError;
+
+    /// Parses a language tag such as `ja` or `zh-hans`, or an English alias such as `japanese` or
+    /// `chinese-simplified`, ignoring ASCII case.
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        match tag.to_ascii_lowercase().as_str() {
+            #[cfg(feature = "ja")]
+            "ja" | "japanese" => Ok(Language::Japanese),
+            #[cfg(feature = "zh-hans")]
+            "zh-hans" | "chinese-simplified" | "simplified-chinese" => Ok(Language::SimplifiedChinese),
+            #[cfg(feature = "zh-hant")]
+            "zh-hant" | "chinese-traditional" | "traditional-chinese" => Ok(Language::TraditionalChinese),
+            #[cfg(feature = "th")]
+            "th" | "thai" => Ok(Language::Thai),
+            _ => Err(LanguageParseError(tag.to_string())),
+        }
+    }
+}
+
+/// An error raised when a language tag is unknown or names a language whose model is not
+/// compiled in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageParseError(String);
+
+impl LanguageParseError {
+    /// Returns the tag that failed to parse.
+    pub fn tag(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for LanguageParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown or disabled language: {}", self.0)
+    }
+}
+
+impl std::error::Error for LanguageParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "ja", feature = "zh-hans"))]
+    fn should_parse_tags_and_aliases() {
+        assert_eq!("ja".parse(), Ok(Language::Japanese));
+        assert_eq!("Japanese".parse(), Ok(Language::Japanese));
+        assert_eq!("zh-hans".parse(), Ok(Language::SimplifiedChinese));
+        assert_eq!("chinese-simplified".parse(), Ok(Language::SimplifiedChinese));
+    }
+
+    #[test]
+    fn should_reject_unknown_tags() {
+        let error = "klingon".parse::<Language>().unwrap_err();
+
+        assert_eq!(error.tag(), "klingon");
+        assert_eq!(error.to_string(), "unknown or disabled language: klingon");
+    }
+}
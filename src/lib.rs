@@ -1,4 +1,55 @@
+#[cfg(test)]
+mod comments;
+mod context;
+#[cfg(feature = "evaluate")]
+mod corpus;
+mod document;
+mod duplicates;
+mod extractor;
+mod hint;
+mod index;
+mod iter;
+mod language;
+#[cfg(feature = "linebreak")]
+mod linebreak;
+#[cfg(feature = "json")]
+mod loader;
+mod local;
+mod mixed;
 pub mod models;
 mod parser;
+mod phrase;
+#[cfg(feature = "score")]
+pub mod scoring;
+mod script;
+mod segmented;
+mod segmenter;
+mod stats;
+mod stream;
+mod trainer;
 
-pub use parser::Parser;
+pub use context::ScoringContext;
+pub use document::Paragraph;
+pub use extractor::{FeatureExtractor, FnExtractor};
+pub use hint::{BreakClass, BreakOpportunity};
+pub use index::{ByteIndex, CharIndex};
+pub use iter::{Boundaries, ChunkIterator, ChunksExact};
+pub use language::{Language, LanguageParseError};
+pub use local::ThreadLocalParser;
+pub use mixed::MixedScriptParser;
+pub use parser::{BreakStrength, DEFAULT_FORCED_BREAKS, Parser, SplitStrategy};
+pub use phrase::PhraseBreaker;
+pub use script::{Script, sentence_script};
+pub use segmenter::{Fallback, RuleBasedSegmenter, Segmenter};
+pub use stats::ParseStats;
+pub use stream::StreamParser;
+pub use trainer::ModelTrainer;
+
+#[cfg(feature = "evaluate")]
+pub use corpus::{Corpus, EvalResult};
+
+#[cfg(feature = "linebreak")]
+pub use linebreak::UnicodeAwareParser;
+
+#[cfg(feature = "json")]
+pub use loader::ModelLoader;
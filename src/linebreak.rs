@@ -0,0 +1,107 @@
+use crate::parser::split_at_boundaries;
+use crate::{CharIndex, Parser, Segmenter};
+use unicode_linebreak::{BreakClass, break_property};
+
+/// A parser that adjusts the BudouX boundaries to the line breaking classes of UAX #14.
+///
+/// Boundaries before characters that may not start a line, of class `NS` (e.g. `々`) or `CJ`
+/// (e.g. small kana, treated as `NS` as in strict line breaking), are removed. A boundary is
+/// added after every character of class `BA` (e.g. `‐` or a tab) unless it would start a line
+/// with such a character.
+pub struct UnicodeAwareParser<'m> {
+    /// The parser providing the semantic boundaries
+    parser: Parser<'m>,
+}
+
+impl<'m> UnicodeAwareParser<'m> {
+    /// Constructs a parser post-filtering the boundaries of another parser.
+    ///
+    /// # Arguments
+    ///
+    /// * `parser` - The parser providing the semantic boundaries.
+    pub fn new(parser: Parser<'m>) -> Self {
+        UnicodeAwareParser { parser }
+    }
+
+    /// Parses the input sentence and returns a list of semantic chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks.
+    #[must_use]
+    pub fn parse<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        if sentence.is_empty() {
+            return Vec::new();
+        }
+
+        split_at_boundaries(sentence, &self.char_boundaries(sentence))
+    }
+
+    /// Parses the input sentence and returns a list of boundaries as character positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The list of boundary positions in characters.
+    #[must_use]
+    pub fn parse_char_boundaries(&self, sentence: &str) -> Vec<CharIndex> {
+        self.char_boundaries(sentence).into_iter().map(CharIndex).collect()
+    }
+
+    /// Same as `parse_char_boundaries`, as plain positions for internal use.
+    fn char_boundaries(&self, sentence: &str) -> Vec<usize> {
+        let classes = sentence.chars().map(|c| break_property(c as u32)).collect::<Vec<_>>();
+        let mut semantic = self.parser.boundaries(sentence).peekable();
+
+        (1..classes.len())
+            .filter(|&i| {
+                let is_semantic = semantic.next_if_eq(&i).is_some();
+                let is_non_starter = matches!(
+                    classes[i],
+                    BreakClass::NonStarter | BreakClass::ConditionalJapaneseStarter
+                );
+                (is_semantic || classes[i - 1] == BreakClass::After) && !is_non_starter
+            })
+            .collect()
+    }
+}
+
+impl Segmenter for UnicodeAwareParser<'_> {
+    fn segment<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        self.parse(s)
+    }
+
+    fn segment_boundaries(&self, s: &str) -> Vec<usize> {
+        self.char_boundaries(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+    use crate::models::Model;
+
+    #[test]
+    fn should_not_break_before_non_starters() {
+        let model = model_from_tuples! { "UW4" => [("々", 10000), ("ょ", 10000), ("z", -20000)] };
+        let parser = UnicodeAwareParser::new(Parser::new(model));
+
+        assert_eq!(parser.parse("人々"), vec!["人々"]);
+        assert_eq!(parser.parse("きょう"), vec!["きょう"]);
+    }
+
+    #[test]
+    fn should_break_after_break_after_characters() {
+        let parser = UnicodeAwareParser::new(Parser::new(Model::new()));
+
+        assert_eq!(parser.parse("a\u{2010}b"), vec!["a\u{2010}", "b"]);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::models::{Model, ModelLoadError};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+#[cfg(feature = "ja")]
+use crate::models::JA_MODEL;
+
+#[cfg(feature = "zh-hans")]
+use crate::models::ZH_HANS_MODEL;
+
+#[cfg(feature = "zh-hant")]
+use crate::models::ZH_HANT_MODEL;
+
+#[cfg(feature = "th")]
+use crate::models::TH_MODEL;
+
+/// A place a model can be loaded from.
+enum Source {
+    Embedded(String),
+    JsonFile(PathBuf),
+    JsonStr(String),
+    EnvVar(String),
+}
+
+/// A builder collecting the sources a model may be loaded from, tried in the order they were
+/// added.
+///
+/// ```no_run
+/// use budoux_rs::ModelLoader;
+///
+/// let model = ModelLoader::new()
+///     .from_env_var("BUDOUX_MODEL")
+///     .from_json_file("model.json")
+///     .load();
+/// ```
+#[derive(Default)]
+pub struct ModelLoader {
+    /// The sources to try, in order
+    sources: Vec<Source>,
+}
+
+// The `from_*` methods name the sources they add rather than converting `self`.
+#[allow(clippy::wrong_self_convention)]
+impl ModelLoader {
+    /// Constructs a loader without any source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a compiled-in default model as a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The feature name of the model, e.g. `ja`; it must be enabled.
+    pub fn from_embedded(mut self, lang: &str) -> Self {
+        self.sources.push(Source::Embedded(lang.to_string()));
+        self
+    }
+
+    /// Adds a BudouX JSON model file as a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file.
+    pub fn from_json_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(Source::JsonFile(path.into()));
+        self
+    }
+
+    /// Adds BudouX JSON text as a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON text.
+    pub fn from_json_str(mut self, json: &str) -> Self {
+        self.sources.push(Source::JsonStr(json.to_string()));
+        self
+    }
+
+    /// Adds a BudouX JSON model file whose path is read from an environment variable at load
+    /// time as a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `var_name` - The name of the environment variable.
+    pub fn from_env_var(mut self, var_name: &str) -> Self {
+        self.sources.push(Source::EnvVar(var_name.to_string()));
+        self
+    }
+
+    /// Loads the model from the first source that succeeds.
+    ///
+    /// # Returns
+    ///
+    /// The model, or the error of the last source if none succeeds.
+    pub fn load(&self) -> Result<Model, ModelLoadError> {
+        let mut last_error = ModelLoadError::Invalid("no model source configured".to_string());
+
+        for source in &self.sources {
+            match load_source(source) {
+                Ok(model) => return Ok(model),
+                Err(e) => last_error = e,
+            }
+        }
+
+        Err(last_error)
+    }
+}
+
+fn load_source(source: &Source) -> Result<Model, ModelLoadError> {
+    match source {
+        Source::Embedded(lang) => embedded(lang),
+        Source::JsonFile(path) => Model::from_json_str(&fs::read_to_string(path)?),
+        Source::JsonStr(json) => Model::from_json_str(json),
+        Source::EnvVar(var_name) => {
+            let path = env::var_os(var_name)
+                .ok_or_else(|| ModelLoadError::Invalid(format!("environment variable `{}` is not set", var_name)))?;
+            Model::from_json_str(&fs::read_to_string(path)?)
+        }
+    }
+}
+
+fn embedded(lang: &str) -> Result<Model, ModelLoadError> {
+    match lang {
+        #[cfg(feature = "ja")]
+        "ja" => Ok(JA_MODEL.clone()),
+        #[cfg(feature = "zh-hans")]
+        "zh-hans" => Ok(ZH_HANS_MODEL.clone()),
+        #[cfg(feature = "zh-hant")]
+        "zh-hant" => Ok(ZH_HANT_MODEL.clone()),
+        #[cfg(feature = "th")]
+        "th" => Ok(TH_MODEL.clone()),
+        _ => Err(ModelLoadError::Invalid(format!(
+            "no embedded `{}` model; is its feature enabled?",
+            lang
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+
+    #[test]
+    fn should_load_from_the_first_working_source() {
+        let model = ModelLoader::new()
+            .from_env_var("BUDOUX_RS_TEST_UNSET_VARIABLE")
+            .from_json_file("/nonexistent/model.json")
+            .from_json_str(r#"{"UW4": {"a": 10}}"#)
+            .from_json_str(r#"{"UW4": {"b": 20}}"#)
+            .load()
+            .unwrap();
+
+        assert_eq!(model, model_from_tuples! { "UW4" => [("a", 10)] });
+    }
+
+    #[test]
+    fn should_report_the_last_error() {
+        assert!(matches!(ModelLoader::new().load(), Err(ModelLoadError::Invalid(_))));
+        assert!(matches!(
+            ModelLoader::new()
+                .from_embedded("xx")
+                .from_json_file("/nonexistent/model.json")
+                .load(),
+            Err(ModelLoadError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::Parser;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+
+/// Source of the keys identifying each `ThreadLocalParser` in the per-thread storage.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// The parsers created on the current thread, keyed by the id of their `ThreadLocalParser`
+    /// and paired with a weak reference that is dead once the handle is dropped.
+    static PARSERS: RefCell<HashMap<usize, (Weak<()>, Parser<'static>)>> = RefCell::new(HashMap::new());
+}
+
+/// A parser handle that lazily builds one `Parser` per thread, so that it can be shared across
+/// threads without wrapping the parser in an `Arc` or synchronizing access to it.
+///
+/// Dropping the handle frees the parser of the dropping thread; those of other threads are freed
+/// the next time the threads parse with any handle, or when they exit.
+pub struct ThreadLocalParser {
+    /// The key of this handle in the per-thread storage
+    id: usize,
+    /// Kept alive by the handle, so that threads can tell that their parser is no longer used
+    alive: Arc<()>,
+    /// Builds the parser of a thread on its first use
+    factory: Box<dyn Fn() -> Parser<'static> + Send + Sync>,
+}
+
+impl ThreadLocalParser {
+    /// Constructs a handle building its per-thread parsers with a factory.
+    ///
+    /// # Arguments
+    ///
+    /// * `factory` - Called once on every thread that parses with this handle.
+    pub fn new(factory: impl Fn() -> Parser<'static> + Send + Sync + 'static) -> Self {
+        ThreadLocalParser {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            alive: Arc::new(()),
+            factory: Box::new(factory),
+        }
+    }
+
+    /// Parses the input sentence with the parser of the current thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks.
+    #[must_use]
+    pub fn parse<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        PARSERS.with(|parsers| {
+            // Dropped outside the borrow, as they may own other handles.
+            let stale = parsers
+                .borrow_mut()
+                .extract_if(|_, (alive, _)| alive.strong_count() == 0)
+                .collect::<Vec<_>>();
+            drop(stale);
+
+            if !parsers.borrow().contains_key(&self.id) {
+                // Built outside the borrow so that the factory may use other handles.
+                let parser = (self.factory)();
+                parsers
+                    .borrow_mut()
+                    .insert(self.id, (Arc::downgrade(&self.alive), parser));
+            }
+
+            parsers.borrow()[&self.id].1.parse(s)
+        })
+    }
+}
+
+impl Drop for ThreadLocalParser {
+    fn drop(&mut self) {
+        // The storage is already gone if the handle is dropped during thread teardown.
+        let _ = PARSERS.try_with(|parsers| parsers.borrow_mut().remove(&self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    #[cfg_attr(target_os = "wasi", ignore = "threads are not supported")]
+    fn should_build_one_parser_per_thread() {
+        let built = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&built);
+        let parser = Arc::new(ThreadLocalParser::new(move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+            Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] })
+        }));
+
+        assert_eq!(parser.parse("ab"), vec!["a", "b"]);
+        assert_eq!(parser.parse("abab"), vec!["a", "ba", "b"]);
+
+        let other = Arc::clone(&parser);
+        thread::spawn(move || assert_eq!(other.parse("ab"), vec!["a", "b"]))
+            .join()
+            .unwrap();
+
+        assert_eq!(built.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "wasi", ignore = "threads are not supported")]
+    fn should_free_the_parsers_of_handles_dropped_on_other_threads() {
+        let dropped = ThreadLocalParser::new(|| Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] }));
+        let kept = ThreadLocalParser::new(|| Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] }));
+        let parsers = || PARSERS.with(|parsers| parsers.borrow().len());
+
+        assert_eq!(dropped.parse("ab"), vec!["a", "b"]);
+        thread::spawn(move || drop(dropped)).join().unwrap();
+        assert_eq!(parsers(), 1);
+
+        assert_eq!(kept.parse("ab"), vec!["a", "b"]);
+        assert_eq!(parsers(), 1);
+    }
+}
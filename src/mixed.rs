@@ -0,0 +1,105 @@
+use crate::script::{Block, block};
+use crate::{Parser, Script, Segmenter, sentence_script};
+use std::collections::HashMap;
+
+/// A parser for mixed-language text that chunks every run of one script with the parser for
+/// that script.
+///
+/// Runs are delimited where the letters change script, with kana and Han characters forming a
+/// single run; digits, punctuation and whitespace stay in the run they follow. The script of
+/// each run is determined by `sentence_script`, and runs without a matching parser are kept as
+/// single chunks.
+pub struct MixedScriptParser<'m> {
+    /// The parser used for each script
+    parsers: HashMap<Script, Parser<'m>>,
+}
+
+impl<'m> MixedScriptParser<'m> {
+    /// Constructs a mixed-script parser.
+    ///
+    /// # Arguments
+    ///
+    /// * `parsers` - The parser used for each script.
+    pub fn new(parsers: HashMap<Script, Parser<'m>>) -> Self {
+        MixedScriptParser { parsers }
+    }
+
+    /// Splits the input text into script runs and parses each run with the parser for its script.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - An input text.
+    ///
+    /// # Returns
+    ///
+    /// The chunks of all runs, which concatenate back to the input text.
+    #[must_use]
+    pub fn parse_mixed<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        script_runs(text)
+            .into_iter()
+            .flat_map(
+                |run| match sentence_script(run).and_then(|script| self.parsers.get(&script)) {
+                    Some(parser) => parser.parse(run),
+                    None => vec![run],
+                },
+            )
+            .collect()
+    }
+}
+
+impl Segmenter for MixedScriptParser<'_> {
+    fn segment<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        self.parse_mixed(s)
+    }
+}
+
+/// Splits a text where its letters change from one block group to an incompatible one.
+fn script_runs(text: &str) -> Vec<&str> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut current: Option<Block> = None;
+
+    for (byte, c) in text.char_indices() {
+        let Some(next) = block(c) else {
+            continue;
+        };
+
+        match current {
+            Some(block) if !block.is_compatible(next) => {
+                runs.push(&text[start..byte]);
+                start = byte;
+            }
+            _ => {}
+        }
+        current = Some(next);
+    }
+    if start < text.len() {
+        runs.push(&text[start..]);
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+
+    #[test]
+    fn should_split_text_into_script_runs() {
+        assert_eq!(script_runs("今日はgood天気。"), vec!["今日は", "good", "天気。"]);
+        assert_eq!(script_runs("「abc」 วันนี้"), vec!["「abc」 ", "วันนี้"]);
+        assert!(script_runs("").is_empty());
+    }
+
+    #[test]
+    fn should_parse_each_run_with_its_parser() {
+        let japanese = Parser::new(model_from_tuples! { "UW4" => [("は", 10000)] });
+        let parser = MixedScriptParser::new(HashMap::from([(Script::Japanese, japanese)]));
+
+        assert_eq!(
+            parser.parse_mixed("今日はgood今日は"),
+            vec!["今日", "は", "good", "今日", "は"]
+        );
+    }
+}
@@ -1,6 +1,778 @@
-use std::collections::HashMap;
+use crate::ScoringContext;
+use crate::duplicates;
+use crate::parser::{FEATURE_WINDOWS, base_score_of_sum, feature_substrings};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io;
+use std::ops::{Deref, DerefMut};
 
-pub type Model = HashMap<String, HashMap<String, i64>>;
+#[cfg(feature = "remote")]
+use std::time::Duration;
+
+/// Time allowed for fetching a model in `Model::from_url`.
+#[cfg(feature = "remote")]
+pub const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// BudouX model data, mapping each feature group (e.g. `UW1`) to the scores of its substrings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Model(HashMap<String, HashMap<String, i64>>);
+
+impl Model {
+    /// Constructs an empty model.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a model from `(key, subkey, value)` entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `iter` - The entries to insert. Later entries overwrite earlier ones with the same key
+    ///   and subkey.
+    pub fn with_entries<'a>(iter: impl IntoIterator<Item = (&'a str, &'a str, i64)>) -> Self {
+        let mut model = Self::new();
+        model.extend(iter);
+        model
+    }
+
+    /// Constructs a model from flat `(group, key, value)` triples, creating the feature groups
+    /// as needed. The function counterpart of `model_from_tuples!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The triples to insert. For duplicate `(group, key)` pairs the last value wins.
+    pub fn from_tuples<'a>(entries: impl IntoIterator<Item = (&'a str, &'a str, i64)>) -> Self {
+        Self::with_entries(entries)
+    }
+
+    /// Unwraps the model into its underlying map of feature groups.
+    pub fn into_inner(self) -> HashMap<String, HashMap<String, i64>> {
+        self.0
+    }
+
+    /// Parses a model from BudouX JSON: an object mapping each feature group to an object of
+    /// integer scores.
+    ///
+    /// Of a repeated feature group or key, the last one is kept; with the `log` feature, a
+    /// warning is logged for each. Use `check_duplicates` to reject such models instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON text.
+    ///
+    /// # Returns
+    ///
+    /// The parsed model, or an error if the text is not a valid BudouX model.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(json: &str) -> Result<Model, ModelLoadError> {
+        use serde_json::Value;
+
+        let Value::Object(groups) = serde_json::from_str::<Value>(json)? else {
+            return Err(ModelLoadError::Invalid(
+                "expected an object of feature groups".to_string(),
+            ));
+        };
+        #[cfg(feature = "log")]
+        warn_duplicates(json);
+
+        let mut model = Model::new();
+        for (key, group) in groups {
+            let Value::Object(entries) = group else {
+                return Err(ModelLoadError::Invalid(format!(
+                    "feature group `{}` is not an object",
+                    key
+                )));
+            };
+
+            let group = model.entry(key.clone()).or_default();
+            for (subkey, value) in entries {
+                let score = value.as_i64().ok_or_else(|| {
+                    ModelLoadError::Invalid(format!("score of `{}` in `{}` is not an integer", subkey, key))
+                })?;
+                group.insert(subkey, score);
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Finds the feature groups and keys repeated in BudouX JSON. JSON parsers, and so
+    /// `from_json_str`, silently keep the last value of a repeated key.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON text.
+    ///
+    /// # Returns
+    ///
+    /// `(group, None)` for every repeated feature group and `(group, Some(key))` for every
+    /// repeated key within a group, in order of appearance.
+    pub fn check_duplicates(json: &str) -> Vec<(String, Option<String>)> {
+        duplicates::duplicate_keys(json)
+    }
+
+    /// Fetches a BudouX JSON model over HTTP, waiting at most `DEFAULT_FETCH_TIMEOUT`.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the model file.
+    ///
+    /// # Returns
+    ///
+    /// The fetched model, or an error if the request fails or the response is not a valid model.
+    #[cfg(feature = "remote")]
+    pub fn from_url(url: &str) -> Result<Model, ModelLoadError> {
+        Self::from_url_with_timeout(url, DEFAULT_FETCH_TIMEOUT)
+    }
+
+    /// Fetches a BudouX JSON model over HTTP with a custom timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL of the model file.
+    /// * `timeout` - The time allowed for the whole request.
+    ///
+    /// # Returns
+    ///
+    /// The fetched model, or an error if the request fails or the response is not a valid model.
+    #[cfg(feature = "remote")]
+    pub fn from_url_with_timeout(url: &str, timeout: Duration) -> Result<Model, ModelLoadError> {
+        let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        let response = agent.get(url).call().map_err(|e| ModelLoadError::Http(Box::new(e)))?;
+
+        Self::from_json_str(&response.into_string()?)
+    }
+
+    /// Iterates over all entries of the model as `(key, subkey, value)` triples, in arbitrary order.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str, i64)> {
+        self.0.iter().flat_map(|(key, group)| {
+            group
+                .iter()
+                .map(move |(subkey, &value)| (key.as_str(), subkey.as_str(), value))
+        })
+    }
+
+    /// Looks up the weight of a single entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The feature group, e.g. `UW4`.
+    /// * `key` - The substring within the group.
+    ///
+    /// # Returns
+    ///
+    /// The weight, or `None` if the model has no such entry.
+    pub fn find_entry(&self, group: &str, key: &str) -> Option<i64> {
+        self.0.get(group).and_then(|group| group.get(key)).copied()
+    }
+
+    /// Returns the number of feature groups, which is 13 for a full BudouX model.
+    pub fn group_count(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the total number of `(key, subkey, value)` entries across all feature groups.
+    pub fn entry_count(&self) -> usize {
+        self.0.values().map(HashMap::len).sum()
+    }
+
+    /// Returns the entries with the largest absolute weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The maximum number of entries to return.
+    ///
+    /// # Returns
+    ///
+    /// Up to `n` `(key, subkey, value)` triples, ordered by decreasing absolute weight and then by
+    /// key and subkey.
+    pub fn top_entries(&self, n: usize) -> Vec<(String, String, i64)> {
+        let mut entries = self.entries().collect::<Vec<_>>();
+        entries.sort_unstable_by(|a, b| {
+            b.2.unsigned_abs()
+                .cmp(&a.2.unsigned_abs())
+                .then_with(|| (a.0, a.1).cmp(&(b.0, b.1)))
+        });
+
+        entries
+            .into_iter()
+            .take(n)
+            .map(|(key, subkey, value)| (key.to_string(), subkey.to_string(), value))
+            .collect()
+    }
+
+    /// Counts the weights of the model in equal-width bins spanning its smallest to its largest
+    /// weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `buckets` - The number of bins.
+    ///
+    /// # Returns
+    ///
+    /// `(lower_bound, count)` pairs in increasing order of the lower bound, or an empty list if
+    /// the model is empty or `buckets` is 0.
+    pub fn weight_histogram(&self, buckets: usize) -> Vec<(i64, usize)> {
+        let (Some(min), Some(max)) = (
+            self.entries().map(|(_, _, value)| value).min(),
+            self.entries().map(|(_, _, value)| value).max(),
+        ) else {
+            return Vec::new();
+        };
+        if buckets == 0 {
+            return Vec::new();
+        }
+
+        let span = i128::from(max) - i128::from(min) + 1;
+        let width = (span + buckets as i128 - 1) / buckets as i128;
+        let mut counts = vec![0; buckets];
+        for (_, _, value) in self.entries() {
+            counts[((i128::from(value) - i128::from(min)) / width) as usize] += 1;
+        }
+
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| ((i128::from(min) + i as i128 * width) as i64, count))
+            .collect()
+    }
+
+    /// Removes the entries that no feature window of any corpus sentence ever looks up, producing
+    /// a smaller model for a specific domain.
+    ///
+    /// The base score of a parser is derived from the sum of all weights, so a parser built from
+    /// the shrunk model can place boundaries differently even on the corpus sentences.
+    ///
+    /// # Arguments
+    ///
+    /// * `corpus` - Sentences representative of the domain.
+    ///
+    /// # Returns
+    ///
+    /// The model restricted to the triggered entries, without feature groups left empty.
+    pub fn shrink(&self, corpus: &[&str]) -> Model {
+        let mut triggered = HashSet::new();
+        for sentence in corpus {
+            let context = ScoringContext::new(sentence);
+            for i in 1..context.len() {
+                triggered.extend(feature_substrings(&context, i));
+            }
+        }
+
+        self.entries()
+            .filter(|&(key, subkey, _)| triggered.contains(&(key, subkey)))
+            .collect()
+    }
+
+    /// Multiplies every weight of a feature group by a factor, shifting the model toward more or
+    /// fewer boundaries without retraining it.
+    ///
+    /// Scaled weights are rounded to the nearest integer. The base score of a parser built
+    /// afterwards follows the new sum of all weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The feature group to scale, such as `TW1`. Unknown groups are ignored.
+    /// * `factor` - The factor to multiply the weights by.
+    pub fn apply_discount(&mut self, group: &str, factor: f64) {
+        if let Some(entries) = self.get_mut(group) {
+            for value in entries.values_mut() {
+                *value = (*value as f64 * factor).round() as i64;
+            }
+        }
+    }
+
+    /// Keeps the entries two models agree on, e.g. to find the strongest signals shared by the
+    /// members of an ensemble.
+    ///
+    /// Entries whose weights differ in sign would cancel out and are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first model.
+    /// * `b` - The second model.
+    ///
+    /// # Returns
+    ///
+    /// The entries present in both models with weights of the same sign, each with the weight of
+    /// smaller magnitude.
+    pub fn intersection(a: &Model, b: &Model) -> Model {
+        a.entries()
+            .filter_map(|(key, subkey, x)| {
+                let y = b.find_entry(key, subkey)?;
+                (x.signum() == y.signum()).then(|| (key, subkey, if x.abs() <= y.abs() { x } else { y }))
+            })
+            .collect()
+    }
+
+    /// Computes the weight changes from one model to another, as a delta model that `merge`
+    /// applies back.
+    ///
+    /// Missing entries count as a weight of 0, so removed entries get the negated old weight.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The old model.
+    /// * `updated` - The new model.
+    ///
+    /// # Returns
+    ///
+    /// The entries whose weight changed, with the difference `updated - base` as weight.
+    pub fn subtract(base: &Model, updated: &Model) -> Model {
+        let removed = base
+            .entries()
+            .filter(|&(key, subkey, _)| updated.find_entry(key, subkey).is_none())
+            .map(|(key, subkey, old)| (key, subkey, -old));
+        let changed = updated.entries().filter_map(|(key, subkey, new)| {
+            let delta = new - base.find_entry(key, subkey).unwrap_or(0);
+            (delta != 0).then_some((key, subkey, delta))
+        });
+
+        removed.chain(changed).collect()
+    }
+
+    /// Adds the weights of a delta model, such as one computed by `subtract`, to a model.
+    ///
+    /// Entries whose weight sums to 0 do not affect scoring and are left out, so merging the
+    /// delta between two models into the first one recovers the second one unless it has
+    /// entries weighing 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - The model to update.
+    /// * `delta` - The weight changes to apply.
+    ///
+    /// # Returns
+    ///
+    /// The model with the weights of both models summed entry by entry.
+    pub fn merge(base: &Model, delta: &Model) -> Model {
+        let added = delta
+            .entries()
+            .filter(|&(key, subkey, _)| base.find_entry(key, subkey).is_none());
+        let updated = base
+            .entries()
+            .map(|(key, subkey, old)| (key, subkey, old + delta.find_entry(key, subkey).unwrap_or(0)));
+
+        added.chain(updated).filter(|&(_, _, value)| value != 0).collect()
+    }
+
+    /// Compares two models entry by entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The old model.
+    /// * `b` - The new model.
+    ///
+    /// # Returns
+    ///
+    /// The entries added, removed and changed in weight when going from `a` to `b`.
+    pub fn diff(a: &Model, b: &Model) -> ModelDiff {
+        let mut diff = ModelDiff::default();
+
+        for (key, group) in a.iter() {
+            for (subkey, &old) in group {
+                match b.find_entry(key, subkey) {
+                    None => diff.removed.push((key.clone(), subkey.clone(), old)),
+                    Some(new) if new != old => diff.changed.push((key.clone(), subkey.clone(), old, new)),
+                    Some(_) => {}
+                }
+            }
+        }
+        for (key, group) in b.iter() {
+            for (subkey, &new) in group {
+                if a.find_entry(key, subkey).is_none() {
+                    diff.added.push((key.clone(), subkey.clone(), new));
+                }
+            }
+        }
+
+        diff.added.sort_unstable();
+        diff.removed.sort_unstable();
+        diff.changed.sort_unstable();
+        diff
+    }
+}
+
+impl fmt::Display for Model {
+    /// Summarizes the model in one line, e.g. for logging; use `{:#?}` for the full contents.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let weights = self.entries().map(|(_, _, value)| value).collect::<Vec<_>>();
+        let range = match (weights.iter().min(), weights.iter().max()) {
+            (Some(min), Some(max)) => format!("[{}, {}]", min, max),
+            _ => "[]".to_string(),
+        };
+        write!(
+            f,
+            "Model {{ groups: {}, entries: {}, weight_range: {}, base_score: {} }}",
+            self.group_count(),
+            self.entry_count(),
+            range,
+            base_score_of_sum(weights.iter().sum())
+        )
+    }
+}
+
+impl Deref for Model {
+    type Target = HashMap<String, HashMap<String, i64>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Model {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<HashMap<String, HashMap<String, i64>>> for Model {
+    fn from(map: HashMap<String, HashMap<String, i64>>) -> Self {
+        Model(map)
+    }
+}
+
+impl From<Model> for HashMap<String, HashMap<String, i64>> {
+    fn from(model: Model) -> Self {
+        model.0
+    }
+}
+
+impl From<HashMap<String, HashMap<String, i32>>> for Model {
+    /// Widens compact `i32` weights to the `i64` scores used at runtime.
+    fn from(map: HashMap<String, HashMap<String, i32>>) -> Self {
+        let map = map
+            .into_iter()
+            .map(|(key, group)| {
+                (
+                    key,
+                    group
+                        .into_iter()
+                        .map(|(subkey, value)| (subkey, value.into()))
+                        .collect(),
+                )
+            })
+            .collect();
+        Model(map)
+    }
+}
+
+impl<'a> Extend<(&'a str, &'a str, i64)> for Model {
+    fn extend<T: IntoIterator<Item = (&'a str, &'a str, i64)>>(&mut self, iter: T) {
+        for (key, subkey, value) in iter {
+            self.0
+                .entry(key.to_string())
+                .or_default()
+                .insert(subkey.to_string(), value);
+        }
+    }
+}
+
+impl<'a> FromIterator<(&'a str, &'a str, i64)> for Model {
+    fn from_iter<T: IntoIterator<Item = (&'a str, &'a str, i64)>>(iter: T) -> Self {
+        Self::with_entries(iter)
+    }
+}
+
+/// Builds a `Model` from feature groups and their `(substring, score)` pairs.
+///
+/// ```
+/// use budoux_rs::model_from_tuples;
+///
+/// let model = model_from_tuples! {
+///     "UW4" => [("a", 10000), ("b", 5000)],
+///     "BW2" => [("ab", 300)],
+/// };
+/// assert_eq!(model["UW4"]["b"], 5000);
+/// ```
+#[macro_export]
+macro_rules! model_from_tuples {
+    ($($group:expr => [$(($key:expr, $value:expr)),* $(,)?]),* $(,)?) => {{
+        let mut model = $crate::models::Model::new();
+        $(
+            #[allow(unused_variables)]
+            let group = model.entry(::std::string::String::from($group)).or_default();
+            $(
+                group.insert(::std::string::String::from($key), $value);
+            )*
+        )*
+        model
+    }};
+}
+
+/// Borrowed BudouX model data, for parsing without copying the model strings, e.g. out of a
+/// memory-mapped file or a `&'static str`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelRef<'a>(HashMap<&'a str, HashMap<&'a str, i64>>);
+
+impl<'a> ModelRef<'a> {
+    /// Constructs an empty borrowed model.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses a borrowed model from BudouX JSON, referencing the keys in place.
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The JSON text, which must outlive the model.
+    ///
+    /// # Returns
+    ///
+    /// The parsed model, or an error if the text is not a valid BudouX model. Keys containing
+    /// escape sequences cannot be borrowed and are reported as an error. Repeated keys are
+    /// handled as by `Model::from_json_str`.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(json: &'a str) -> Result<ModelRef<'a>, ModelLoadError> {
+        let model = serde_json::from_str(json)?;
+        #[cfg(feature = "log")]
+        warn_duplicates(json);
+        Ok(ModelRef(model))
+    }
+
+    /// Looks up the weight of a single entry, like `Model::find_entry`.
+    pub fn find_entry(&self, group: &str, key: &str) -> Option<i64> {
+        self.0.get(group).and_then(|group| group.get(key)).copied()
+    }
+
+    /// Iterates over all entries of the model as `(key, subkey, value)` triples, in arbitrary order.
+    pub fn entries(&self) -> impl Iterator<Item = (&'a str, &'a str, i64)> {
+        self.0
+            .iter()
+            .flat_map(|(&key, group)| group.iter().map(move |(&subkey, &value)| (key, subkey, value)))
+    }
+}
+
+impl<'a> Deref for ModelRef<'a> {
+    type Target = HashMap<&'a str, HashMap<&'a str, i64>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ModelRef<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'a> From<HashMap<&'a str, HashMap<&'a str, i64>>> for ModelRef<'a> {
+    fn from(map: HashMap<&'a str, HashMap<&'a str, i64>>) -> Self {
+        ModelRef(map)
+    }
+}
+
+impl<'a> From<&'a Model> for ModelRef<'a> {
+    fn from(model: &'a Model) -> Self {
+        let map = model
+            .iter()
+            .map(|(key, group)| {
+                (
+                    key.as_str(),
+                    group.iter().map(|(subkey, &value)| (subkey.as_str(), value)).collect(),
+                )
+            })
+            .collect();
+        ModelRef(map)
+    }
+}
+
+/// Logs a warning for every feature group or key repeated in BudouX JSON, scanning the text only
+/// if warnings are enabled.
+#[cfg(all(feature = "json", feature = "log"))]
+fn warn_duplicates(json: &str) {
+    if !log::log_enabled!(log::Level::Warn) {
+        return;
+    }
+
+    for (group, key) in Model::check_duplicates(json) {
+        match key {
+            None => log::warn!(
+                "duplicate feature group `{}` in model JSON, keeping the last one",
+                group
+            ),
+            Some(key) => log::warn!(
+                "duplicate key `{}` in `{}` in model JSON, keeping the last one",
+                key,
+                group
+            ),
+        }
+    }
+}
+
+/// An error raised while loading a model at runtime.
+#[derive(Debug)]
+pub enum ModelLoadError {
+    /// The model data could not be read.
+    Io(io::Error),
+    /// The model data is not valid JSON.
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// The HTTP request for the model failed.
+    #[cfg(feature = "remote")]
+    Http(Box<ureq::Error>),
+    /// The model data is well-formed but does not describe a BudouX model.
+    Invalid(String),
+    /// BudouX does not provide a model for the language yet.
+    NotYetAvailable(&'static str),
+}
+
+impl fmt::Display for ModelLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelLoadError::Io(e) => write!(f, "failed to read model: {}", e),
+            #[cfg(feature = "json")]
+            ModelLoadError::Json(e) => write!(f, "failed to parse model JSON: {}", e),
+            #[cfg(feature = "remote")]
+            ModelLoadError::Http(e) => write!(f, "failed to fetch model: {}", e),
+            ModelLoadError::Invalid(message) => write!(f, "invalid model: {}", message),
+            ModelLoadError::NotYetAvailable(lang) => write!(f, "no BudouX model is available for {} yet", lang),
+        }
+    }
+}
+
+impl std::error::Error for ModelLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ModelLoadError::Io(e) => Some(e),
+            #[cfg(feature = "json")]
+            ModelLoadError::Json(e) => Some(e),
+            #[cfg(feature = "remote")]
+            ModelLoadError::Http(e) => Some(e),
+            ModelLoadError::Invalid(_) | ModelLoadError::NotYetAvailable(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for ModelLoadError {
+    fn from(e: io::Error) -> Self {
+        ModelLoadError::Io(e)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for ModelLoadError {
+    fn from(e: serde_json::Error) -> Self {
+        ModelLoadError::Json(e)
+    }
+}
+
+/// The differences between two models, as produced by `Model::diff`.
+///
+/// Entries are `(key, subkey, weight)` triples sorted by key and subkey.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelDiff {
+    /// Entries present only in the new model
+    pub added: Vec<(String, String, i64)>,
+    /// Entries present only in the old model
+    pub removed: Vec<(String, String, i64)>,
+    /// Entries present in both models with different weights, as `(key, subkey, old, new)`
+    pub changed: Vec<(String, String, i64, i64)>,
+}
+
+impl ModelDiff {
+    /// Checks whether the two compared models were identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Summarizes the differences in a single line, e.g. `"3 added, 1 removed, 2 changed"`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} added, {} removed, {} changed",
+            self.added.len(),
+            self.removed.len(),
+            self.changed.len()
+        )
+    }
+}
+
+/// A builder for constructing models entry by entry in code, e.g. in tests or examples.
+///
+/// ```
+/// use budoux_rs::models::ModelBuilder;
+///
+/// let model = ModelBuilder::new()
+///     .add("UW4", "学", 1500)
+///     .add("BW2", "生で", 800)
+///     .build()
+///     .unwrap();
+/// assert_eq!(model.entry_count(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ModelBuilder {
+    /// Entries added so far, in order
+    entries: Vec<(String, String, i64)>,
+}
+
+impl ModelBuilder {
+    /// Constructs a builder without entries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an entry to the model, replacing any earlier entry with the same group and key.
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The feature group, such as `UW4`.
+    /// * `key` - The substring scored by the group.
+    /// * `value` - The weight of the entry.
+    ///
+    /// # Returns
+    ///
+    /// The builder with the entry added.
+    pub fn add(mut self, group: &str, key: &str, value: i64) -> Self {
+        self.entries.push((group.to_string(), key.to_string(), value));
+        self
+    }
+
+    /// Builds the model from the added entries.
+    ///
+    /// # Returns
+    ///
+    /// The model, or an error if no entry was added or an entry uses a group that is not one of
+    /// `FEATURE_WINDOWS`.
+    pub fn build(self) -> Result<Model, ModelBuilderError> {
+        if self.entries.is_empty() {
+            return Err(ModelBuilderError::Empty);
+        }
+        if let Some((group, _, _)) = self
+            .entries
+            .iter()
+            .find(|(group, _, _)| !FEATURE_WINDOWS.iter().any(|&(name, _, _)| name == group))
+        {
+            return Err(ModelBuilderError::UnknownGroup(group.clone()));
+        }
+
+        Ok(self
+            .entries
+            .iter()
+            .map(|(group, key, value)| (group.as_str(), key.as_str(), *value))
+            .collect())
+    }
+}
+
+/// An error raised by `ModelBuilder::build`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModelBuilderError {
+    /// No entry was added.
+    Empty,
+    /// An entry uses a feature group the parser does not look up.
+    UnknownGroup(String),
+}
+
+impl fmt::Display for ModelBuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ModelBuilderError::Empty => write!(f, "the model has no entries"),
+            ModelBuilderError::UnknownGroup(group) => write!(f, "unknown feature group `{}`", group),
+        }
+    }
+}
+
+impl std::error::Error for ModelBuilderError {}
 
 #[cfg(feature = "ja")]
 include!(concat!(env!("OUT_DIR"), "/models/ja.rs"));
@@ -13,3 +785,288 @@ include!(concat!(env!("OUT_DIR"), "/models/zh_hant.rs"));
 
 #[cfg(feature = "th")]
 include!(concat!(env!("OUT_DIR"), "/models/th.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_report_added_removed_and_changed_entries() {
+        let a = Model::with_entries([("UW4", "a", 100), ("UW4", "b", 200), ("BW2", "ab", 300)]);
+        let b = Model::with_entries([("UW4", "a", 100), ("UW4", "b", 250), ("TW1", "abc", 400)]);
+
+        let diff = Model::diff(&a, &b);
+
+        assert_eq!(diff.added, vec![("TW1".to_string(), "abc".to_string(), 400)]);
+        assert_eq!(diff.removed, vec![("BW2".to_string(), "ab".to_string(), 300)]);
+        assert_eq!(diff.changed, vec![("UW4".to_string(), "b".to_string(), 200, 250)]);
+        assert_eq!(diff.summary(), "1 added, 1 removed, 1 changed");
+        assert!(Model::diff(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn should_build_a_model_from_entries() {
+        let mut model = Model::with_entries([("UW4", "学", 1000), ("UW4", "生", 200)]);
+        model.extend([("BW2", "生で", 500), ("UW4", "生", 300)]);
+
+        assert_eq!(model.len(), 2);
+        assert_eq!(model["UW4"]["学"], 1000);
+        assert_eq!(model["UW4"]["生"], 300);
+        assert_eq!(model["BW2"]["生で"], 500);
+    }
+
+    #[test]
+    fn should_build_a_model_from_tuples() {
+        let model = model_from_tuples! {
+            "UW4" => [("a", 10000), ("b", 5000)],
+            "BW2" => [],
+        };
+
+        assert_eq!(model.len(), 2);
+        assert_eq!(model["UW4"]["a"], 10000);
+        assert_eq!(model["UW4"]["b"], 5000);
+        assert!(model["BW2"].is_empty());
+    }
+
+    #[test]
+    fn should_widen_i32_weights() {
+        let mut group = HashMap::new();
+        group.insert("a".to_string(), i32::MIN);
+        let mut map = HashMap::new();
+        map.insert("UW4".to_string(), group);
+
+        let model = Model::from(map);
+
+        assert_eq!(model, Model::with_entries([("UW4", "a", i64::from(i32::MIN))]));
+    }
+
+    #[test]
+    fn should_return_the_most_influential_entries() {
+        let model = Model::with_entries([
+            ("UW4", "a", 100),
+            ("UW4", "b", -300),
+            ("BW2", "ab", 300),
+            ("TW1", "abc", 200),
+        ]);
+
+        assert_eq!(
+            model.top_entries(3),
+            vec![
+                ("BW2".to_string(), "ab".to_string(), 300),
+                ("UW4".to_string(), "b".to_string(), -300),
+                ("TW1".to_string(), "abc".to_string(), 200),
+            ]
+        );
+        assert_eq!(model.top_entries(10).len(), 4);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn should_parse_and_validate_json_models() {
+        let model = Model::from_json_str(r#"{"UW4": {"a": 100}, "BW2": {}}"#).unwrap();
+
+        assert_eq!(model["UW4"]["a"], 100);
+        assert!(model["BW2"].is_empty());
+        assert!(matches!(Model::from_json_str("[]"), Err(ModelLoadError::Invalid(_))));
+        assert!(matches!(
+            Model::from_json_str(r#"{"UW4": {"a": 1.5}}"#),
+            Err(ModelLoadError::Invalid(_))
+        ));
+        assert!(matches!(Model::from_json_str("{"), Err(ModelLoadError::Json(_))));
+        assert_eq!(
+            Model::from_json_str(r#"{"UW4": {"a": 1, "a": 2}}"#).unwrap()["UW4"]["a"],
+            2
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn should_borrow_keys_from_json() {
+        let json = r#"{"UW4": {"a": 100}, "BW2": {"ab": -5}}"#.to_string();
+        let model = ModelRef::from_json_str(&json).unwrap();
+
+        assert_eq!(model["UW4"]["a"], 100);
+        assert_eq!(model["BW2"]["ab"], -5);
+        assert!(ModelRef::from_json_str(r#"{"UW4": {"\u3042": 1}}"#).is_err());
+    }
+
+    #[test]
+    fn should_round_trip_through_the_inner_map() {
+        let model = [("UW4", "a", 10), ("BW2", "ab", -5)].into_iter().collect::<Model>();
+        let map = model.clone().into_inner();
+
+        assert_eq!(map["BW2"]["ab"], -5);
+        assert_eq!(Model::from(map), model);
+    }
+
+    #[test]
+    fn should_count_groups_and_entries() {
+        let model = model_from_tuples! {
+            "UW4" => [("a", 10000), ("b", 5000)],
+            "BW2" => [("ab", 300)],
+            "TW1" => [],
+        };
+
+        assert_eq!(model.group_count(), 3);
+        assert_eq!(model.entry_count(), 3);
+    }
+
+    #[test]
+    fn should_find_single_entries() {
+        let model = model_from_tuples! { "UW4" => [("a", 10000)] };
+
+        assert_eq!(model.find_entry("UW4", "a"), Some(10000));
+        assert_eq!(model.find_entry("UW4", "b"), None);
+        assert_eq!(model.find_entry("UW1", "a"), None);
+    }
+
+    #[test]
+    fn should_keep_the_last_duplicate_from_tuples() {
+        let model = Model::from_tuples([("UW4", "a", 1), ("UW4", "a", 2), ("UW1", "a", 3)]);
+
+        assert_eq!(model, model_from_tuples! { "UW4" => [("a", 2)], "UW1" => [("a", 3)] });
+    }
+
+    #[test]
+    fn should_bin_weights_into_equal_width_buckets() {
+        let model = model_from_tuples! {
+            "UW4" => [("a", -100), ("b", -90), ("c", 0), ("d", 95), ("e", 99)],
+        };
+
+        assert_eq!(model.weight_histogram(2), vec![(-100, 2), (0, 3)]);
+        assert_eq!(model.weight_histogram(4), vec![(-100, 2), (-50, 0), (0, 1), (50, 2)]);
+        assert!(model.weight_histogram(0).is_empty());
+        assert!(Model::new().weight_histogram(3).is_empty());
+    }
+
+    #[test]
+    fn should_shrink_to_the_entries_triggered_by_the_corpus() {
+        let model = model_from_tuples! {
+            "UW4" => [("b", 100), ("x", 200)],
+            "BW2" => [("ab", 300), ("xy", 400)],
+            "TW1" => [("xyz", 500)],
+        };
+
+        assert_eq!(
+            model.shrink(&["abc"]),
+            model_from_tuples! { "UW4" => [("b", 100)], "BW2" => [("ab", 300)] }
+        );
+        assert_eq!(model.shrink(&[]), Model::new());
+    }
+
+    #[test]
+    fn should_discount_a_single_group() {
+        let mut model = model_from_tuples! {
+            "UW4" => [("a", 1000), ("b", -5)],
+            "TW1" => [("abc", 300)],
+        };
+
+        model.apply_discount("UW4", 0.5);
+        model.apply_discount("BW2", 0.0);
+
+        assert_eq!(
+            model,
+            model_from_tuples! { "UW4" => [("a", 500), ("b", -3)], "TW1" => [("abc", 300)] }
+        );
+    }
+
+    #[test]
+    fn should_find_duplicate_groups_and_keys() {
+        let json = r#"{"UW4": {"a": 1, "b": 2, "a": 3}, "BW2": {"a": 1}, "UW4": {}, "TW1": {"\"": 1, "\"": 2}}"#;
+
+        assert_eq!(
+            Model::check_duplicates(json),
+            vec![
+                ("UW4".to_string(), Some("a".to_string())),
+                ("UW4".to_string(), None),
+                ("TW1".to_string(), Some("\\\"".to_string())),
+            ]
+        );
+        assert!(Model::check_duplicates(r#"{"UW4": {"a": 1}, "BW2": {"a": 1}}"#).is_empty());
+    }
+
+    #[test]
+    fn should_build_models_fluently() {
+        let model = ModelBuilder::new()
+            .add("UW4", "学", 1500)
+            .add("BW2", "生で", 800)
+            .add("TW1", "私は学", 2000)
+            .add("UW4", "学", 1000)
+            .build();
+
+        assert_eq!(
+            model,
+            Ok(model_from_tuples! {
+                "UW4" => [("学", 1000)],
+                "BW2" => [("生で", 800)],
+                "TW1" => [("私は学", 2000)],
+            })
+        );
+        assert_eq!(ModelBuilder::new().build(), Err(ModelBuilderError::Empty));
+        assert_eq!(
+            ModelBuilder::new().add("UW4", "a", 1).add("XW1", "a", 1).build(),
+            Err(ModelBuilderError::UnknownGroup("XW1".to_string()))
+        );
+    }
+
+    #[test]
+    fn should_intersect_models_agreeing_in_sign() {
+        let a = model_from_tuples! {
+            "UW4" => [("a", 100), ("b", -300), ("c", 50)],
+            "BW2" => [("ab", 200)],
+        };
+        let b = model_from_tuples! {
+            "UW4" => [("a", 400), ("b", -100), ("c", -50)],
+            "TW1" => [("abc", 200)],
+        };
+
+        assert_eq!(
+            Model::intersection(&a, &b),
+            model_from_tuples! { "UW4" => [("a", 100), ("b", -100)] }
+        );
+        assert_eq!(Model::intersection(&a, &Model::new()), Model::new());
+    }
+
+    #[test]
+    fn should_recover_the_updated_model_from_the_delta() {
+        let base = model_from_tuples! {
+            "UW4" => [("a", 100), ("b", -300), ("c", 50)],
+            "BW2" => [("ab", 200)],
+        };
+        let updated = model_from_tuples! {
+            "UW4" => [("a", 400), ("c", 50)],
+            "BW2" => [("ab", 150)],
+            "TW1" => [("abc", 200)],
+        };
+
+        let delta = Model::subtract(&base, &updated);
+
+        assert_eq!(
+            delta,
+            model_from_tuples! {
+                "UW4" => [("a", 300), ("b", 300)],
+                "BW2" => [("ab", -50)],
+                "TW1" => [("abc", 200)],
+            }
+        );
+        assert_eq!(Model::merge(&base, &delta), updated);
+        assert_eq!(Model::subtract(&base, &base), Model::new());
+    }
+
+    #[test]
+    fn should_display_a_summary() {
+        let model = model_from_tuples! {
+            "UW4" => [("a", -100), ("b", 300)],
+            "BW2" => [("ab", 200)],
+        };
+
+        assert_eq!(
+            model.to_string(),
+            "Model { groups: 2, entries: 3, weight_range: [-100, 300], base_score: -200 }"
+        );
+        assert_eq!(
+            Model::new().to_string(),
+            "Model { groups: 0, entries: 0, weight_range: [], base_score: 0 }"
+        );
+    }
+}
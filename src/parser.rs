@@ -14,7 +14,22 @@ See the License for the specific language governing permissions and
 limitations under the License.
 */
 
-use crate::models::Model;
+use crate::hint::MANDATORY_BREAKS;
+use crate::models::{Model, ModelRef};
+use crate::script::is_latin;
+use crate::{
+    Boundaries, BreakClass, BreakOpportunity, ByteIndex, CharIndex, ChunkIterator, ChunksExact, FeatureExtractor,
+    FnExtractor, Language, Paragraph, ParseStats, RuleBasedSegmenter, ScoringContext, Segmenter, StreamParser,
+};
+use std::cmp::Reverse;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::ops::Range;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+#[cfg(any(feature = "ja", feature = "zh-hans", feature = "zh-hant", feature = "th"))]
+use std::sync::LazyLock;
 
 #[cfg(feature = "ja")]
 use crate::models::JA_MODEL;
@@ -28,25 +43,332 @@ use crate::models::ZH_HANT_MODEL;
 #[cfg(feature = "th")]
 use crate::models::TH_MODEL;
 
+#[cfg(feature = "lo")]
+use crate::models::ModelLoadError;
+
+/// Punctuation marks after which `Parser::parse_punct` always inserts a boundary by default.
+pub const DEFAULT_FORCED_BREAKS: &[char] = &['。', '、', '！', '？', '…'];
+
+/// U+200B ZERO WIDTH SPACE, treated as a manual break point by `Parser::parse` if enabled.
+const ZERO_WIDTH_SPACE: char = '\u{200B}';
+
+/// U+FEFF BYTE ORDER MARK, dropped from the start of the sentence by `Parser::parse` if enabled.
+const BYTE_ORDER_MARK: char = '\u{FEFF}';
+
+/// Full-width marks ending a sentence, as split on by `Parser::parse_sentence_aware` and
+/// `PhraseBreaker`.
+pub(crate) const SENTENCE_DELIMITERS: [char; 3] = ['。', '！', '？'];
+
+/// Number of characters from a position on read by the widest feature windows (`UW6` and `TW4`).
+pub(crate) const LOOKAHEAD: usize = 3;
+
+/// Number of characters before a position read by the widest feature windows (`UW1` and `TW1`).
+pub(crate) const LOOKBEHIND: usize = 3;
+
+/// The model feature groups with the character window each one covers, relative to the scored
+/// position.
+pub const FEATURE_WINDOWS: [(&str, isize, isize); 13] = [
+    ("UW1", -3, -2),
+    ("UW2", -2, -1),
+    ("UW3", -1, 0),
+    ("UW4", 0, 1),
+    ("UW5", 1, 2),
+    ("UW6", 2, 3),
+    ("BW1", -2, 0),
+    ("BW2", -1, 1),
+    ("BW3", 0, 2),
+    ("TW1", -3, 0),
+    ("TW2", -2, 1),
+    ("TW3", -1, 2),
+    ("TW4", 0, 3),
+];
+
+/// How `Parser::parse_rebalanced` splits chunks that exceed the length limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplitStrategy {
+    /// Split into pieces of the given number of characters (at least 1).
+    EveryN(usize),
+    /// Split after whitespace and full-width punctuation, as `RuleBasedSegmenter` does.
+    AtPunctuation,
+    /// Split into pieces of two characters.
+    BigramSplit,
+}
+
+/// The `strength` of the SSML `<break/>` elements written by `Parser::parse_to_ssml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BreakStrength {
+    /// `none`, no pause
+    None,
+    /// `x-weak`
+    XWeak,
+    /// `weak`
+    Weak,
+    /// `medium`
+    Medium,
+    /// `strong`
+    Strong,
+    /// `x-strong`
+    XStrong,
+}
+
+impl BreakStrength {
+    /// Returns the value of the `strength` attribute.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BreakStrength::None => "none",
+            BreakStrength::XWeak => "x-weak",
+            BreakStrength::Weak => "weak",
+            BreakStrength::Medium => "medium",
+            BreakStrength::Strong => "strong",
+            BreakStrength::XStrong => "x-strong",
+        }
+    }
+}
+
+/// Model data of a parser, either owned (possibly shared with other parsers) or borrowed.
+enum ModelData<'a> {
+    Owned(Arc<Model>),
+    Borrowed(ModelRef<'a>),
+    /// An owned model moved into maps with the faster `ahash` hasher
+    #[cfg(feature = "ahash")]
+    Hashed(ahash::AHashMap<String, ahash::AHashMap<String, i64>>),
+}
+
+impl ModelData<'_> {
+    /// Wraps a model owned by a single parser, rehashing it with `ahash` when that feature is
+    /// enabled.
+    fn from_model(model: Model) -> Self {
+        #[cfg(feature = "ahash")]
+        {
+            ModelData::Hashed(
+                model
+                    .into_inner()
+                    .into_iter()
+                    .map(|(key, group)| (key, group.into_iter().collect()))
+                    .collect(),
+            )
+        }
+        #[cfg(not(feature = "ahash"))]
+        {
+            ModelData::Owned(Arc::new(model))
+        }
+    }
+
+    /// Looks up the score of a substring in a feature group.
+    fn get(&self, key: &str, value: &str) -> Option<i64> {
+        match self {
+            ModelData::Owned(model) => model.find_entry(key, value),
+            ModelData::Borrowed(model) => model.find_entry(key, value),
+            #[cfg(feature = "ahash")]
+            ModelData::Hashed(model) => model.get(key).and_then(|group| group.get(value)).copied(),
+        }
+    }
+
+    /// Iterates over the `(key, subkey, value)` triples of the model, in arbitrary order.
+    fn entries(&self) -> Box<dyn Iterator<Item = (&str, &str, i64)> + '_> {
+        match self {
+            ModelData::Owned(model) => Box::new(model.entries()),
+            ModelData::Borrowed(model) => Box::new(model.entries()),
+            #[cfg(feature = "ahash")]
+            ModelData::Hashed(model) => Box::new(model.iter().flat_map(|(key, group)| {
+                group
+                    .iter()
+                    .map(move |(subkey, &value)| (key.as_str(), subkey.as_str(), value))
+            })),
+        }
+    }
+
+    /// Collects the `(key, subkey, value)` triples of the model, sorted.
+    fn sorted_entries(&self) -> Vec<(&str, &str, i64)> {
+        let mut entries = self.entries().collect::<Vec<_>>();
+        entries.sort_unstable();
+        entries
+    }
+
+    /// Computes the base score for the model, which offsets the sum of all its scores.
+    fn base_score(&self) -> i64 {
+        base_score_of_sum(self.entries().map(|(_, _, value)| value).sum())
+    }
+}
+
 /// A parser for BudouX that provides semantic chunking functionality.
-pub struct Parser {
+///
+/// The lifetime is that of the model data when it is borrowed (see `with_ref_model`); parsers
+/// owning their model are `Parser<'static>`.
+pub struct Parser<'a> {
     /// BudouX model data
-    model: Model,
+    model: ModelData<'a>,
     /// Base score for boundary determination
     base_score: i64,
+    /// Characters that are always followed by a boundary in `parse_punct`
+    forced_breaks: Vec<char>,
+    /// Additional score sources added at every position
+    extractors: Vec<Arc<dyn FeatureExtractor>>,
+    /// Whether `parse` breaks at and strips U+200B ZERO WIDTH SPACE
+    zero_width_space_as_break: bool,
+    /// Whether `parse` drops a leading U+FEFF BYTE ORDER MARK
+    strip_bom: bool,
+    /// Decides whether a score makes a boundary, if not simply by being positive
+    score_normalizer: Option<Arc<dyn Fn(i64) -> bool + Send + Sync>>,
 }
 
-impl Parser {
+impl<'m> Parser<'m> {
     /// Constructs a BudouX parser.
     ///
     /// # Arguments
     ///
     /// * `model` - A model containing scoring data for boundary determination.
     pub fn new(model: Model) -> Self {
-        let s = model.values().flat_map(|group| group.values()).sum::<i64>();
-        let base_score = -((s + 1) / 2);
+        Self::with_model_data(ModelData::from_model(model))
+    }
+
+    /// Constructs a BudouX parser sharing its model with other parsers instead of copying it.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - A shared model containing scoring data for boundary determination.
+    pub fn with_shared_model(model: Arc<Model>) -> Self {
+        Self::with_model_data(ModelData::Owned(model))
+    }
+
+    /// Constructs a BudouX parser that borrows its model data instead of owning it.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - A borrowed model containing scoring data for boundary determination.
+    pub fn with_ref_model(model: ModelRef<'m>) -> Parser<'m> {
+        Self::with_model_data(ModelData::Borrowed(model))
+    }
+
+    fn with_model_data(model: ModelData<'m>) -> Self {
+        Parser {
+            base_score: model.base_score(),
+            model,
+            forced_breaks: DEFAULT_FORCED_BREAKS.to_vec(),
+            extractors: Vec::new(),
+            zero_width_space_as_break: false,
+            strip_bom: false,
+            score_normalizer: None,
+        }
+    }
+
+    /// Replaces the model in place and recomputes the base score.
+    ///
+    /// Other settings of the parser are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `model` - The new model.
+    pub fn reset_with_model(&mut self, model: Model) {
+        self.model = ModelData::from_model(model);
+        self.base_score = self.model.base_score();
+    }
+
+    /// Adds a feature extractor whose score is added to the model score at every position.
+    ///
+    /// Extractors are compared and hashed by identity, so a parser with an extractor is only
+    /// equal to itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `extractor` - The extractor to add.
+    ///
+    /// # Returns
+    ///
+    /// The parser with the extractor added.
+    pub fn with_extra_features(mut self, extractor: Box<dyn FeatureExtractor>) -> Self {
+        self.extractors.push(Arc::from(extractor));
+        self
+    }
+
+    /// Adds a closure as a feature extractor (see `with_extra_features`).
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function taking the sentence and a character position and returning the extra
+    ///   score for a boundary before that position.
+    ///
+    /// # Returns
+    ///
+    /// The parser with the extractor added.
+    pub fn with_fn_extractor(self, f: impl Fn(&str, usize) -> i64 + Send + Sync + 'static) -> Self {
+        self.with_extra_features(Box::new(FnExtractor::new(f)))
+    }
+
+    /// Makes `parse` respect zero width spaces (U+200B) inserted as manual break points.
+    ///
+    /// When enabled, the zero width spaces are removed before scoring, so that the feature
+    /// windows see the text around them as contiguous, and a boundary is always placed where
+    /// they were. They are not part of the returned chunks.
+    ///
+    /// The option applies to `parse`, `chunks`, `chunk_count`, `is_boundary_at`,
+    /// `parse_char_boundaries`, `write_conll` and the `Segmenter` impl, which all agree on the
+    /// chunks. Their boundary positions count the characters without the zero width spaces.
+    /// Methods returning byte or UTF-16 offsets into the sentence and the other `parse_*`
+    /// variants score the sentence as it is.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to treat zero width spaces as boundaries.
+    ///
+    /// # Returns
+    ///
+    /// The parser with the option set.
+    pub fn with_zero_width_space_as_break(mut self, enabled: bool) -> Self {
+        self.zero_width_space_as_break = enabled;
+        self
+    }
+
+    /// Makes `parse` drop the byte order mark (U+FEFF) that texts read from files may start with.
+    ///
+    /// When enabled, a leading byte order mark is neither scored nor part of the returned chunks,
+    /// so the chunks are the same as for the text without it.
+    ///
+    /// The option applies to the same methods as `with_zero_width_space_as_break`, whose
+    /// boundary positions do not count the byte order mark either.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to strip a leading byte order mark.
+    ///
+    /// # Returns
+    ///
+    /// The parser with the option set.
+    pub fn with_bom_strip(mut self, enabled: bool) -> Self {
+        self.strip_bom = enabled;
+        self
+    }
+
+    /// Replaces the rule deciding which scores make a boundary, which is `score > 0` by default.
+    ///
+    /// The normalizer applies to every method placing boundaries by score, e.g. to require a
+    /// margin with `|score| score > 100`. It is compared and hashed by identity, so a parser with
+    /// a normalizer is only equal to itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - A function taking the score of a position and returning whether to break there.
+    ///
+    /// # Returns
+    ///
+    /// The parser with the normalizer set.
+    pub fn with_score_normalizer(mut self, f: impl Fn(i64) -> bool + Send + Sync + 'static) -> Self {
+        self.score_normalizer = Some(Arc::new(f));
+        self
+    }
 
-        Parser { model, base_score }
+    /// Replaces the punctuation marks used by `parse_punct`.
+    ///
+    /// # Arguments
+    ///
+    /// * `chars` - Characters after which a boundary is always inserted.
+    ///
+    /// # Returns
+    ///
+    /// The parser with the new set of forced-break characters.
+    pub fn with_forced_breaks(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.forced_breaks = chars.into_iter().collect();
+        self
     }
 
     /// Parses the input sentence and returns a list of semantic chunks.
@@ -57,26 +379,62 @@ impl Parser {
     ///
     /// # Returns
     ///
-    /// The retrieved chunks.
+    /// The retrieved chunks: none for an empty sentence, otherwise at least one. A single
+    /// character has no position to break at and is always returned as one chunk.
+    #[must_use]
     pub fn parse<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
-        if sentence.is_empty() {
-            return Vec::new();
+        let scored = self.scored_text(sentence);
+        scored.split(&self.scored_boundaries(&scored))
+    }
+
+    /// Parses the input sentence like `parse`, but returns ASCII-only input as a single chunk
+    /// without scoring it.
+    ///
+    /// The default models carry almost no ASCII features, so this skips work that would not
+    /// produce boundaries anyway. With a model or extractor that does score ASCII text, the result
+    /// differs from `parse`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks.
+    #[must_use]
+    pub fn parse_ascii_passthrough<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        if !sentence.is_empty() && sentence.is_ascii() {
+            return vec![sentence];
         }
 
-        let boundaries = self.parse_boundaries(sentence);
-        let mut result = Vec::new();
-        let mut start = 0;
+        self.parse(sentence)
+    }
 
-        for &boundary in &boundaries {
-            result.push(sentence.substring(start, boundary));
-            start = boundary;
+    /// Parses the input sentence unless it is written entirely in ASCII or Latin script, which
+    /// the BudouX models do not segment meaningfully.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks, or `None` if the sentence (including an empty one) should be used
+    /// as is.
+    #[must_use]
+    pub fn try_parse<'a>(&self, sentence: &'a str) -> Option<Vec<&'a str>> {
+        if sentence.chars().all(is_latin) {
+            return None;
         }
-        result.push(sentence.substring(start, sentence.len()));
 
-        result
+        Some(self.parse(sentence))
     }
 
-    /// Parses the input sentence and returns a list of boundaries.
+    /// Parses the input sentence and classifies the break after every piece, combining semantic
+    /// boundaries with hard line breaks.
+    ///
+    /// The sentence is split at the semantic boundaries and after every hard line break
+    /// character (U+000A, U+0085, U+2028 and U+2029).
     ///
     /// # Arguments
     ///
@@ -84,145 +442,2389 @@ impl Parser {
     ///
     /// # Returns
     ///
-    /// The list of boundary positions.
-    pub fn parse_boundaries(&self, sentence: &str) -> Vec<usize> {
-        let mut result = Vec::new();
+    /// The pieces of the sentence, each with the kind of break that follows it.
+    #[must_use]
+    pub fn parse_with_linebreak_hints<'a>(&self, sentence: &'a str) -> Vec<BreakOpportunity<'a>> {
+        if sentence.is_empty() {
+            return Vec::new();
+        }
+
         let chars = sentence.chars().collect::<Vec<_>>();
+        let mut semantic = self.text_boundaries(sentence).into_iter().peekable();
+        let boundaries = (1..chars.len())
+            .filter(|&i| semantic.next_if_eq(&i).is_some() || MANDATORY_BREAKS.contains(&chars[i - 1]))
+            .collect::<Vec<_>>();
 
-        for i in 1..chars.len() {
-            let mut score = self.base_score;
+        let mut starts = vec![0];
+        starts.extend(&boundaries);
+        split_at_boundaries(sentence, &boundaries)
+            .into_iter()
+            .zip(starts)
+            .map(|(text, start)| {
+                let end = start + text.chars().count();
+                let break_class = if end == chars.len() || MANDATORY_BREAKS.contains(&chars[end - 1]) {
+                    BreakClass::Mandatory
+                } else if MANDATORY_BREAKS.contains(&chars[end]) {
+                    BreakClass::Prohibited
+                } else {
+                    BreakClass::Allowed
+                };
+                BreakOpportunity { text, break_class }
+            })
+            .collect()
+    }
 
-            score += self.get_score("UW1", sentence.substring(i.saturating_sub(3), i.saturating_sub(2)));
-            score += self.get_score("UW2", sentence.substring(i.saturating_sub(2), i.saturating_sub(1)));
-            score += self.get_score("UW3", sentence.substring(i.saturating_sub(1), i));
-            score += self.get_score("UW4", sentence.substring(i, i.saturating_add(1)));
-            score += self.get_score("UW5", sentence.substring(i.saturating_add(1), i.saturating_add(2)));
-            score += self.get_score("UW6", sentence.substring(i.saturating_add(2), i.saturating_add(3)));
-            score += self.get_score("BW1", sentence.substring(i.saturating_sub(2), i));
-            score += self.get_score("BW2", sentence.substring(i.saturating_sub(1), i.saturating_add(1)));
-            score += self.get_score("BW3", sentence.substring(i, i.saturating_add(2)));
-            score += self.get_score("TW1", sentence.substring(i.saturating_sub(3), i));
-            score += self.get_score("TW2", sentence.substring(i.saturating_sub(2), i.saturating_add(1)));
-            score += self.get_score("TW3", sentence.substring(i.saturating_sub(1), i.saturating_add(2)));
-            score += self.get_score("TW4", sentence.substring(i, i.saturating_add(3)));
+    /// Starts parsing a text that arrives in pieces, e.g. from a network socket.
+    ///
+    /// # Returns
+    ///
+    /// A stream parser emitting the chunks of the text fed to it as soon as they are complete.
+    pub fn parse_chunked_stream<'a>(&'a self) -> StreamParser<'a> {
+        StreamParser::new(self)
+    }
 
-            if score > 0 {
-                result.push(i);
+    /// Returns a lazy iterator over the semantic chunks of the input sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// An iterator scoring positions only as far as needed for each chunk. When zero width
+    /// spaces are dropped as enabled by `with_zero_width_space_as_break`, the chunks are
+    /// computed upfront instead.
+    pub fn chunks<'a>(&'a self, sentence: &'a str) -> ChunkIterator<'a> {
+        match self.scored_text(sentence) {
+            ScoredText::Slice(text) => ChunkIterator::new(self, text),
+            scored @ ScoredText::Stripped { .. } => {
+                ChunkIterator::from_chunks(self, scored.split(&self.scored_boundaries(&scored)))
             }
         }
-
-        result
     }
 
-    /// Gets the score for a given key and value from the model.
+    /// Returns an iterator over the semantic chunks of the input sentence, computing all
+    /// boundaries upfront so that the number of chunks is known.
     ///
     /// # Arguments
     ///
-    /// * `key` - The model feature group key.
-    /// * `value` - The specific substring to score.
+    /// * `sentence` - An input sentence.
     ///
     /// # Returns
     ///
-    /// The score value or 0 if not found.
-    fn get_score(&self, key: &str, value: &str) -> i64 {
-        self.model.get(key).and_then(|map| map.get(value)).copied().unwrap_or(0)
+    /// An exact-size iterator over the chunks.
+    pub fn chunks_exact<'a>(&self, sentence: &'a str) -> ChunksExact<'a> {
+        ChunksExact::new(sentence, self.byte_boundaries(sentence))
     }
 
-    /// Loads a parser equipped with the default Japanese model.
+    /// Parses only the `sentence[start_byte..end_byte]` range of the input sentence.
+    ///
+    /// Feature windows may extend past the range into the rest of the sentence, so the chunks are
+    /// the same as the corresponding part of `parse(sentence)` with the range edges as extra
+    /// boundaries.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `start_byte` - The start of the range in bytes.
+    /// * `end_byte` - The end of the range in bytes.
     ///
     /// # Returns
     ///
-    /// A parser with the default Japanese model.
-    #[cfg(feature = "ja")]
-    pub fn load_default_japanese_parser() -> Self {
-        Self::new(JA_MODEL.to_owned())
+    /// The retrieved chunks, borrowed from `sentence`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds or does not lie on character boundaries.
+    #[must_use]
+    pub fn parse_between<'a>(&self, sentence: &'a str, start_byte: usize, end_byte: usize) -> Vec<&'a str> {
+        let range = &sentence[start_byte..end_byte];
+        if range.is_empty() {
+            return Vec::new();
+        }
+
+        let start_char = sentence[..start_byte].chars().count();
+        let end_char = start_char + range.chars().count();
+        let context = ScoringContext::new(sentence);
+        let boundaries = (start_char + 1..end_char)
+            .filter(|&i| self.is_boundary_score(self.score_in_context(&context, i)))
+            .map(|i| i - start_char)
+            .collect::<Vec<_>>();
+
+        split_at_boundaries(range, &boundaries)
     }
 
-    /// Loads a parser equipped with the default Simplified Chinese model.
+    /// Parses the input sentence, letting a predicate decide which positions become boundaries.
+    ///
+    /// The predicate is called for every position between two characters, not only those with a
+    /// positive score, so `|_, score| score > 0` reproduces `parse` unless a score normalizer is
+    /// set.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `keep` - Called with the character position and its score; returns whether to break there.
     ///
     /// # Returns
     ///
-    /// A parser with the default Simplified Chinese model.
-    #[cfg(feature = "zh-hans")]
-    pub fn load_default_simplified_chinese_parser() -> Self {
-        Self::new(ZH_HANS_MODEL.to_owned())
+    /// The retrieved chunks.
+    #[must_use]
+    pub fn parse_with_position_filter<'a, F>(&self, sentence: &'a str, keep: F) -> Vec<&'a str>
+    where
+        F: Fn(usize, i64) -> bool,
+    {
+        if sentence.is_empty() {
+            return Vec::new();
+        }
+
+        let context = ScoringContext::new(sentence);
+        let boundaries = (1..context.len())
+            .filter(|&i| keep(i, self.score_in_context(&context, i)))
+            .collect::<Vec<_>>();
+        split_at_boundaries(sentence, &boundaries)
     }
 
-    /// Loads a parser equipped with the default Traditional Chinese model.
+    /// Parses the input sentence without breaking inside the given byte ranges, e.g. to keep URLs
+    /// or inline markup in one chunk.
+    ///
+    /// Boundaries at the edges of a range are kept, and ranges may overlap.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `ranges` - The byte ranges of the sentence to keep unbroken.
     ///
     /// # Returns
     ///
-    /// A parser with the default Traditional Chinese model.
-    #[cfg(feature = "zh-hant")]
-    pub fn load_default_traditional_chinese_parser() -> Self {
-        Self::new(ZH_HANT_MODEL.to_owned())
+    /// The retrieved chunks.
+    #[must_use]
+    pub fn parse_with_skip_ranges<'a>(&self, sentence: &'a str, ranges: &[Range<usize>]) -> Vec<&'a str> {
+        let offsets = sentence.char_indices().map(|(offset, _)| offset).collect::<Vec<_>>();
+        self.parse_with_position_filter(sentence, |i, score| {
+            self.is_boundary_score(score)
+                && !ranges
+                    .iter()
+                    .any(|range| range.start < offsets[i] && offsets[i] < range.end)
+        })
     }
 
-    /// Loads a parser equipped with the default Thai model.
+    /// Parses the input sentence without breaking inside the matches of a regular expression,
+    /// e.g. URLs, hashtags or mentions.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `pattern` - The pattern whose non-overlapping matches are kept unbroken.
     ///
     /// # Returns
     ///
-    /// A parser with the default Thai model.
-    #[cfg(feature = "th")]
-    pub fn load_default_thai_parser() -> Self {
-        Self::new(TH_MODEL.to_owned())
+    /// The retrieved chunks.
+    #[cfg(feature = "regex")]
+    #[must_use]
+    pub fn parse_with_exclusion_regex<'a>(&self, sentence: &'a str, pattern: &regex::Regex) -> Vec<&'a str> {
+        let ranges = pattern.find_iter(sentence).map(|m| m.range()).collect::<Vec<_>>();
+        self.parse_with_skip_ranges(sentence, &ranges)
     }
-}
-
-trait Substring {
-    fn substring(&self, start: usize, end: usize) -> &str;
-}
 
-impl Substring for str {
-    #[inline]
-    fn substring(&self, start: usize, end: usize) -> &str {
-        let char_indices = self.char_indices().collect::<Vec<_>>();
-        let start_byte = char_indices.get(start).map(|(byte, _)| *byte).unwrap_or(self.len());
-        let end_byte = char_indices.get(end).map(|(byte, _)| *byte).unwrap_or(self.len());
-
-        &self[start_byte..end_byte]
+    /// Splits the input text into sentences at `。`, `！` and `？` and parses each sentence.
+    ///
+    /// Each delimiter stays attached to the end of the sentence it closes.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - An input text.
+    ///
+    /// # Returns
+    ///
+    /// The chunks of every sentence, one list per sentence.
+    #[must_use]
+    pub fn parse_sentence_aware<'a>(&self, text: &'a str) -> Vec<Vec<&'a str>> {
+        text.split_inclusive(SENTENCE_DELIMITERS)
+            .map(|sentence| self.parse(sentence))
+            .collect()
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashMap;
-
-    const TEST_SENTENCE: &str = "abcdeabcd";
-
-    #[test]
-    fn should_separate_if_a_strong_feature_item_supports() {
-        let mut model = HashMap::new();
-        let mut uw4 = HashMap::new();
-        uw4.insert("a".to_string(), 10000);
-        model.insert("UW4".to_string(), uw4);
 
-        let parser = Parser::new(model);
-        let result = parser.parse(TEST_SENTENCE);
+    /// Parses a batch of sentences into `(start_char, end_char, chunk)` triples, e.g. for tabular
+    /// display or spreadsheet export.
+    ///
+    /// The positions are character offsets into each sentence, at the boundaries returned by
+    /// `parse_char_boundaries`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentences` - The input sentences.
+    ///
+    /// # Returns
+    ///
+    /// The chunks of every sentence with their character ranges, one list per sentence and none
+    /// for an empty sentence.
+    #[must_use]
+    pub fn parse_table<'a>(&self, sentences: &[&'a str]) -> Vec<Vec<(usize, usize, &'a str)>> {
+        sentences
+            .iter()
+            .map(|&sentence| {
+                if sentence.is_empty() {
+                    return Vec::new();
+                }
 
-        assert_eq!(result, vec!["abcde", "abcd"]);
+                let boundaries = self.text_boundaries(sentence);
+                let starts = [0].into_iter().chain(boundaries.iter().copied());
+                let ends = boundaries.iter().copied().chain([sentence.chars().count()]);
+                starts
+                    .zip(ends)
+                    .zip(split_at_boundaries(sentence, &boundaries))
+                    .map(|((start, end), chunk)| (start, end, chunk))
+                    .collect()
+            })
+            .collect()
     }
 
-    #[test]
-    fn should_separate_even_if_it_makes_a_phrase_of_one_character() {
-        let mut model = HashMap::new();
-        let mut uw4 = HashMap::new();
-        uw4.insert("b".to_string(), 10000);
-        model.insert("UW4".to_string(), uw4);
-
-        let parser = Parser::new(model);
-        let result = parser.parse(TEST_SENTENCE);
+    /// Returns the first semantic chunk of the input sentence, scoring positions only up to the
+    /// first boundary.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The leading chunk, or the whole sentence if it has no boundary.
+    #[must_use]
+    pub fn parse_first_chunk<'a>(&self, sentence: &'a str) -> &'a str {
+        match self.boundaries(sentence).next() {
+            Some(boundary) => sentence.substring(0, boundary),
+            None => sentence,
+        }
+    }
+
+    /// Returns the last semantic chunk of the input sentence, scoring positions backwards from
+    /// the end only down to the last boundary.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The trailing chunk, or the whole sentence if it has no boundary.
+    #[must_use]
+    pub fn parse_last_chunk<'a>(&self, sentence: &'a str) -> &'a str {
+        let context = ScoringContext::new(sentence);
+        match (1..context.len())
+            .rev()
+            .find(|&i| self.is_boundary_score(self.score_in_context(&context, i)))
+        {
+            Some(boundary) => sentence.substring(boundary, sentence.len()),
+            None => sentence,
+        }
+    }
+
+    /// Splits a document into paragraphs at blank lines, i.e. runs of two or more line breaks
+    /// (`\n` or `\r\n`), each paragraph into sentences as `parse_sentence_aware` does, and each
+    /// sentence into chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `document` - An input document.
+    ///
+    /// # Returns
+    ///
+    /// The non-empty paragraphs of the document.
+    #[must_use]
+    pub fn parse_document<'a>(&self, document: &'a str) -> Vec<Paragraph<'a>> {
+        split_paragraphs(document)
+            .into_iter()
+            .map(|paragraph| Paragraph {
+                sentences: self.parse_sentence_aware(paragraph),
+            })
+            .collect()
+    }
+
+    /// Parses the input sentence with the end of a preceding sentence as context.
+    ///
+    /// Up to 3 characters of `context` are put in front of `sentence` so that the feature windows
+    /// of its first characters see them, but no boundary is placed between the two.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The text preceding the sentence, e.g. the previous sentence.
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks, covering only `sentence`.
+    #[must_use]
+    pub fn parse_with_context<'a>(&self, context: &str, sentence: &'a str) -> Vec<&'a str> {
+        if sentence.is_empty() {
+            return Vec::new();
+        }
+
+        let start = context.char_indices().rev().nth(2).map_or(0, |(byte, _)| byte);
+        let prefix = &context[start..];
+        let prefix_len = prefix.chars().count();
+        let combined = format!("{prefix}{sentence}");
+        let end_char = prefix_len + sentence.chars().count();
+        let context = ScoringContext::new(&combined);
+        let boundaries = (prefix_len + 1..end_char)
+            .filter(|&i| self.is_boundary_score(self.score_in_context(&context, i)))
+            .map(|i| i - prefix_len)
+            .collect::<Vec<_>>();
+
+        split_at_boundaries(sentence, &boundaries)
+    }
+
+    /// Parses the input sentence and returns a list of semantic chunks, additionally breaking
+    /// after every forced-break punctuation mark (see `with_forced_breaks`).
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks.
+    #[must_use]
+    pub fn parse_punct<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        if sentence.is_empty() {
+            return Vec::new();
+        }
+
+        let len = sentence.chars().count();
+        let mut boundaries = self.text_boundaries(sentence);
+        boundaries.extend(
+            sentence
+                .chars()
+                .enumerate()
+                .filter(|&(i, c)| i + 1 < len && self.forced_breaks.contains(&c))
+                .map(|(i, _)| i + 1),
+        );
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        split_at_boundaries(sentence, &boundaries)
+    }
+
+    /// Parses the input sentence and further splits every chunk longer than `max_chars`
+    /// characters using the fallback strategy.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `max_chars` - The maximum length of a chunk, in characters, that is kept as is.
+    /// * `fallback` - The strategy for splitting longer chunks.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks.
+    #[must_use]
+    pub fn parse_rebalanced<'a>(&self, sentence: &'a str, max_chars: usize, fallback: SplitStrategy) -> Vec<&'a str> {
+        let mut result = Vec::new();
+
+        for chunk in self.parse(sentence) {
+            if chunk.chars().count() <= max_chars {
+                result.push(chunk);
+                continue;
+            }
+
+            match fallback {
+                SplitStrategy::EveryN(n) => result.extend(split_every(chunk, n.max(1))),
+                SplitStrategy::AtPunctuation => result.extend(RuleBasedSegmenter.segment(chunk)),
+                SplitStrategy::BigramSplit => result.extend(split_every(chunk, 2)),
+            }
+        }
+
+        result
+    }
+
+    /// Parses the input sentence keeping only its `max` highest-scoring boundaries, e.g. for a
+    /// display with room for a fixed number of lines.
+    ///
+    /// Boundaries with equal scores are preferred from the start of the sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `max` - The maximum number of boundaries to keep.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks: at most `max + 1`, or all of them if the sentence has fewer
+    /// boundaries.
+    #[must_use]
+    pub fn parse_max_boundaries<'a>(&self, sentence: &'a str, max: usize) -> Vec<&'a str> {
+        if sentence.is_empty() {
+            return Vec::new();
+        }
+
+        let context = ScoringContext::new(sentence);
+        let mut scored = (1..context.len())
+            .map(|i| (i, self.score_in_context(&context, i)))
+            .filter(|&(_, score)| self.is_boundary_score(score))
+            .collect::<Vec<_>>();
+        scored.sort_by_key(|&(i, score)| (Reverse(score), i));
+
+        let mut boundaries = scored.into_iter().take(max).map(|(i, _)| i).collect::<Vec<_>>();
+        boundaries.sort_unstable();
+        split_at_boundaries(sentence, &boundaries)
+    }
+
+    /// Parses the input sentence into about `n_lines` chunks of lengths as even as possible, e.g.
+    /// for lines of similar width.
+    ///
+    /// Only the boundaries found by `parse_char_boundaries` are used; among them, the ones
+    /// minimizing the sum of the squared chunk lengths in characters are chosen.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `n_lines` - The number of chunks to aim for.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks: `n_lines` of them, or fewer if the sentence has fewer boundaries.
+    #[must_use]
+    pub fn parse_evenly<'a>(&self, sentence: &'a str, n_lines: usize) -> Vec<&'a str> {
+        if sentence.is_empty() {
+            return Vec::new();
+        }
+
+        // The allowed boundaries, framed by the start and the end of the sentence
+        let points = [0]
+            .into_iter()
+            .chain(self.text_boundaries(sentence))
+            .chain([sentence.chars().count()])
+            .collect::<Vec<_>>();
+        let chunks = n_lines.clamp(1, points.len() - 1);
+
+        // `cost[c][j]` is the smallest sum of squared lengths of `c` chunks ending at `points[j]`,
+        // and `previous[c][j]` the point where the last of them starts.
+        let mut cost = vec![vec![None; points.len()]; chunks + 1];
+        let mut previous = vec![vec![0; points.len()]; chunks + 1];
+        cost[0][0] = Some(0);
+        for c in 1..=chunks {
+            for j in c..points.len() {
+                for i in c - 1..j {
+                    let Some(before) = cost[c - 1][i] else {
+                        continue;
+                    };
+                    let total = before + (points[j] - points[i]).pow(2);
+                    if cost[c][j].is_none_or(|best| total < best) {
+                        cost[c][j] = Some(total);
+                        previous[c][j] = i;
+                    }
+                }
+            }
+        }
+
+        let mut boundaries = Vec::with_capacity(chunks - 1);
+        let mut j = points.len() - 1;
+        for c in (2..=chunks).rev() {
+            j = previous[c][j];
+            boundaries.push(points[j]);
+        }
+        boundaries.reverse();
+        split_at_boundaries(sentence, &boundaries)
+    }
+
+    /// Parses the input sentence after trimming it and collapsing every run of whitespace into a
+    /// single space.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks of the normalized sentence.
+    #[must_use = "call parse_and_join if you only need the joined string"]
+    pub fn parse_normalized(&self, sentence: &str) -> Vec<String> {
+        let normalized = sentence.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.parse(&normalized).into_iter().map(str::to_string).collect()
+    }
+
+    /// Parses overlapping windows of the input sentence.
+    ///
+    /// Windows of `window_chars` characters start every `step_chars` characters, until one
+    /// reaches the end of the sentence. Each window is parsed on its own.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `window_chars` - The length of each window in characters.
+    /// * `step_chars` - The distance between the starts of consecutive windows in characters
+    ///   (at least 1).
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks of each window.
+    #[must_use]
+    pub fn parse_sliding_window<'a>(
+        &self,
+        sentence: &'a str,
+        window_chars: usize,
+        step_chars: usize,
+    ) -> Vec<Vec<&'a str>> {
+        let mut result = Vec::new();
+        if sentence.is_empty() || window_chars == 0 {
+            return result;
+        }
+
+        let len = sentence.chars().count();
+        let mut start = 0;
+        loop {
+            let end = (start + window_chars).min(len);
+            result.push(self.parse(sentence.substring(start, end)));
+            if end == len {
+                break;
+            }
+            start += step_chars.max(1);
+        }
+
+        result
+    }
+
+    /// Returns the semantic chunk containing the character at the given position.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `char_pos` - A character position, e.g. of a cursor.
+    ///
+    /// # Returns
+    ///
+    /// The chunk, or `None` if the position is past the end of the sentence.
+    #[must_use]
+    pub fn parse_chunk_at<'a>(&self, sentence: &'a str, char_pos: usize) -> Option<&'a str> {
+        let len = sentence.chars().count();
+        if char_pos >= len {
+            return None;
+        }
+
+        let boundaries = self.text_boundaries(sentence);
+        let next = boundaries.partition_point(|&boundary| boundary <= char_pos);
+        let start = next.checked_sub(1).map_or(0, |i| boundaries[i]);
+        let end = boundaries.get(next).copied().unwrap_or(len);
+
+        Some(sentence.substring(start, end))
+    }
+
+    /// Parses the input sentence and returns the semantic chunks along with summary metrics.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks and their metrics.
+    #[must_use]
+    pub fn parse_with_stats<'a>(&self, sentence: &'a str) -> (Vec<&'a str>, ParseStats) {
+        let chunks = self.parse(sentence);
+        let stats = ParseStats::from_chunks(&chunks);
+        (chunks, stats)
+    }
+
+    /// Parses the input sentence and joins the semantic chunks with a separator.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `separator` - The string inserted between chunks, e.g. `"<wbr>"`.
+    ///
+    /// # Returns
+    ///
+    /// The joined chunks.
+    #[must_use]
+    pub fn parse_and_join(&self, sentence: &str, separator: &str) -> String {
+        self.parse(sentence).join(separator)
+    }
+
+    /// Parses the input sentence and wraps every semantic chunk in a `<span>` element.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence, as plain text; it is HTML-escaped in the output.
+    /// * `class` - The value of the `class` attribute of every span.
+    /// * `inline_block` - Whether to add `style="display:inline-block"`, which keeps each chunk
+    ///   on one line.
+    ///
+    /// # Returns
+    ///
+    /// The HTML fragment.
+    #[must_use]
+    pub fn parse_to_html_span(&self, sentence: &str, class: &str, inline_block: bool) -> String {
+        let style = if inline_block {
+            " style=\"display:inline-block\""
+        } else {
+            ""
+        };
+        let class = escape_html(class);
+
+        self.parse(sentence)
+            .into_iter()
+            .map(|chunk| format!("<span class=\"{}\"{}>{}</span>", class, style, escape_html(chunk)))
+            .collect()
+    }
+
+    /// Parses the input sentence and wraps every semantic chunk in a `<ruby>` element annotated
+    /// with the text returned by `annotation_fn`, e.g. its reading.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence, as plain text; it is HTML-escaped in the output.
+    /// * `annotation_fn` - Maps each chunk to its ruby text, which is HTML-escaped as well.
+    ///
+    /// # Returns
+    ///
+    /// The HTML fragment, with one `<ruby><rb>chunk</rb><rt>annotation</rt></ruby>` per chunk.
+    #[must_use]
+    pub fn parse_to_ruby(&self, sentence: &str, annotation_fn: impl Fn(&str) -> String) -> String {
+        self.parse(sentence)
+            .into_iter()
+            .map(|chunk| {
+                format!(
+                    "<ruby><rb>{}</rb><rt>{}</rt></ruby>",
+                    escape_html(chunk),
+                    escape_html(&annotation_fn(chunk))
+                )
+            })
+            .collect()
+    }
+
+    /// Parses the input sentence into an SSML document with a prosodic break between chunks,
+    /// for text-to-speech engines.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence, as plain text; it is escaped in the output.
+    /// * `break_strength` - The `strength` of every `<break/>`.
+    ///
+    /// # Returns
+    ///
+    /// The SSML document, e.g. `<speak>chunk1<break strength="weak"/>chunk2</speak>`.
+    #[must_use]
+    pub fn parse_to_ssml(&self, sentence: &str, break_strength: BreakStrength) -> String {
+        let separator = format!("<break strength=\"{}\"/>", break_strength.as_str());
+        let chunks = self.parse(sentence).into_iter().map(escape_html).collect::<Vec<_>>();
+        format!("<speak>{}</speak>", chunks.join(&separator))
+    }
+
+    /// Decodes the HTML character references in a text extracted from HTML and parses the result.
+    ///
+    /// Numeric references and the most common named ones (e.g. `&amp;` or `&mdash;`) are
+    /// decoded; unknown references are kept as they are. Tags are not handled.
+    ///
+    /// # Arguments
+    ///
+    /// * `html_text` - A text that may contain HTML character references.
+    ///
+    /// # Returns
+    ///
+    /// The chunks of the decoded text.
+    #[must_use = "call parse_and_join if you only need the joined string"]
+    pub fn parse_html_text(&self, html_text: &str) -> Vec<String> {
+        let text = decode_html_entities(html_text);
+        self.parse(&text).into_iter().map(str::to_string).collect()
+    }
+
+    /// Inserts `<wbr>` between the semantic chunks of the prose in a Markdown document.
+    ///
+    /// Fenced code blocks, inline code spans, links and images are copied as they are, and prose
+    /// is parsed line by line. Other Markdown syntax is treated as prose.
+    ///
+    /// # Arguments
+    ///
+    /// * `markdown` - A Markdown document.
+    ///
+    /// # Returns
+    ///
+    /// The document with `<wbr>` inserted at the chunk boundaries of its prose.
+    #[must_use]
+    pub fn parse_markdown(&self, markdown: &str) -> String {
+        let mut output = String::with_capacity(markdown.len());
+        let mut fence = None;
+
+        for line in markdown.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\r', '\n']);
+            match (fence, code_fence(content)) {
+                (None, Some(marker)) => fence = Some(marker),
+                (Some(open), Some(marker)) if marker.starts_with(open) && content.trim_end().ends_with(marker) => {
+                    fence = None
+                }
+                (None, None) => {
+                    self.push_markdown_prose(content, &mut output);
+                    output.push_str(&line[content.len()..]);
+                    continue;
+                }
+                _ => {}
+            }
+            output.push_str(line);
+        }
+
+        output
+    }
+
+    /// Appends a line of Markdown prose with `<wbr>` inserted between the chunks of its text,
+    /// copying inline code spans, links and images as they are.
+    fn push_markdown_prose(&self, line: &str, output: &mut String) {
+        let mut prose_start = 0;
+        let mut i = 0;
+
+        while let Some(c) = line[i..].chars().next() {
+            let rest = &line[i..];
+            let span_len = match c {
+                '\\' => {
+                    i += 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+                    continue;
+                }
+                '`' => {
+                    let run = rest.len() - rest.trim_start_matches('`').len();
+                    // An unclosed run of backticks is literal text.
+                    let len = code_span_len(rest, run);
+                    if len.is_none() {
+                        i += run;
+                        continue;
+                    }
+                    len
+                }
+                '[' => link_len(rest, 0),
+                '!' if rest[1..].starts_with('[') => link_len(rest, 1),
+                _ => None,
+            };
+
+            match span_len {
+                Some(len) => {
+                    output.push_str(&self.parse_and_join(&line[prose_start..i], "<wbr>"));
+                    output.push_str(&rest[..len]);
+                    i += len;
+                    prose_start = i;
+                }
+                None => i += c.len_utf8(),
+            }
+        }
+
+        output.push_str(&self.parse_and_join(&line[prose_start..], "<wbr>"));
+    }
+
+    /// Returns the length in characters of the longest semantic chunk of the input sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The number of characters in the longest chunk, or 0 for an empty sentence.
+    #[must_use]
+    pub fn parse_max_chunk_chars(&self, sentence: &str) -> usize {
+        let len = sentence.chars().count();
+        max_span(self.text_boundaries(sentence).into_iter().chain([len]))
+    }
+
+    /// Returns the length in bytes of the longest semantic chunk of the input sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes in the longest chunk, or 0 for an empty sentence.
+    #[must_use]
+    pub fn parse_max_chunk_bytes(&self, sentence: &str) -> usize {
+        max_span(self.byte_boundaries(sentence).into_iter().chain([sentence.len()]))
+    }
+
+    /// Writes the semantic chunks of the input sentence in the CoNLL-U format.
+    ///
+    /// Each chunk becomes one token line; all annotation columns other than `ID` and `FORM`
+    /// are left unspecified (`_`), except `MISC`, which carries `SpaceAfter=No` so that the
+    /// original sentence can be reconstructed from the tokens. An empty sentence has no tokens
+    /// and writes nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `w` - The writer to output to.
+    ///
+    /// # Returns
+    ///
+    /// An I/O error if writing fails, or one of kind `InvalidInput`, before anything is written,
+    /// if the sentence contains a tab or a line break, which the line- and tab-separated format
+    /// cannot represent.
+    pub fn write_conll<W: Write>(&self, sentence: &str, w: &mut W) -> io::Result<()> {
+        if let Some(c) = sentence
+            .chars()
+            .find(|&c| c == '\t' || c == '\r' || MANDATORY_BREAKS.contains(&c))
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("CoNLL-U cannot represent {c:?} in a sentence"),
+            ));
+        }
+        if sentence.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(w, "# text = {}", sentence)?;
+
+        let chunks = self.parse(sentence);
+        for (i, chunk) in chunks.iter().enumerate() {
+            let misc = if i + 1 < chunks.len() { "SpaceAfter=No" } else { "_" };
+            writeln!(w, "{}\t{}\t_\t_\t_\t_\t_\t_\t_\t{}", i + 1, chunk, misc)?;
+        }
+
+        writeln!(w)
+    }
+
+    /// Parses the input sentence and returns a list of boundaries.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The list of boundary positions in characters.
+    #[deprecated(note = "use `parse_char_boundaries` or `parse_byte_boundaries` instead")]
+    #[must_use]
+    pub fn parse_boundaries(&self, sentence: &str) -> Vec<usize> {
+        self.char_boundaries(sentence)
+    }
+
+    /// Parses the input sentence and returns a list of boundaries as byte offsets, which can be
+    /// used to slice the sentence directly.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The list of boundary positions in bytes.
+    #[must_use]
+    pub fn parse_byte_boundaries(&self, sentence: &str) -> Vec<ByteIndex> {
+        self.byte_boundaries(sentence).into_iter().map(ByteIndex).collect()
+    }
+
+    /// Same as `parse_byte_boundaries`, as plain offsets for internal use.
+    fn byte_boundaries(&self, sentence: &str) -> Vec<usize> {
+        let offsets = sentence.char_indices().map(|(byte, _)| byte).collect::<Vec<_>>();
+        self.text_boundaries(sentence).into_iter().map(|i| offsets[i]).collect()
+    }
+
+    /// Parses the input sentence and returns a list of boundaries as character (Unicode scalar
+    /// value) positions.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The list of boundary positions in characters.
+    #[must_use]
+    pub fn parse_char_boundaries(&self, sentence: &str) -> Vec<CharIndex> {
+        self.char_boundaries(sentence).into_iter().map(CharIndex).collect()
+    }
+
+    /// Same as `parse_char_boundaries`, as plain positions for internal use. The positions are
+    /// those where `parse` splits, in characters of the sentence without the characters it drops.
+    pub(crate) fn char_boundaries(&self, sentence: &str) -> Vec<usize> {
+        self.scored_boundaries(&self.scored_text(sentence))
+    }
+
+    /// Drops a leading byte order mark and the zero width spaces from the sentence if
+    /// `with_bom_strip` and `with_zero_width_space_as_break` are enabled.
+    fn scored_text<'a>(&self, mut sentence: &'a str) -> ScoredText<'a> {
+        if self.strip_bom {
+            sentence = sentence.strip_prefix(BYTE_ORDER_MARK).unwrap_or(sentence);
+        }
+        if !self.zero_width_space_as_break || !sentence.contains(ZERO_WIDTH_SPACE) {
+            return ScoredText::Slice(sentence);
+        }
+
+        let mut text = String::with_capacity(sentence.len());
+        let mut ranges = Vec::new();
+        let mut forced = Vec::new();
+        for (byte, c) in sentence.char_indices() {
+            if c == ZERO_WIDTH_SPACE {
+                forced.push(ranges.len());
+            } else {
+                text.push(c);
+                ranges.push(byte..byte + c.len_utf8());
+            }
+        }
+
+        ScoredText::Stripped {
+            sentence,
+            text,
+            ranges,
+            forced,
+        }
+    }
+
+    /// Computes the boundaries of a scored text, adding those forced by dropped characters.
+    fn scored_boundaries(&self, scored: &ScoredText<'_>) -> Vec<usize> {
+        let mut boundaries = self.text_boundaries(scored.text());
+        if let ScoredText::Stripped { ranges, forced, .. } = scored {
+            boundaries.extend(forced.iter().copied().filter(|&i| i > 0 && i < ranges.len()));
+            boundaries.sort_unstable();
+            boundaries.dedup();
+        }
+
+        boundaries
+    }
+
+    /// Computes the boundaries of the text as it is, ignoring the options that drop characters.
+    /// Every method computing all the boundaries of a text goes through here exactly once, so
+    /// that the span, the metrics and the ordering check cover them all.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self, sentence), fields(sentence_len_chars = sentence.chars().count()))
+    )]
+    pub(crate) fn text_boundaries(&self, sentence: &str) -> Vec<usize> {
+        let boundaries = self.boundaries(sentence).collect::<Vec<_>>();
+        debug_assert!(
+            boundaries.windows(2).all(|pair| pair[0] < pair[1]),
+            "boundaries must be strictly increasing"
+        );
+
+        #[cfg(feature = "metrics")]
+        {
+            metrics::counter!("budoux.parse.calls").increment(1);
+            metrics::histogram!("budoux.parse.boundaries").record(boundaries.len() as f64);
+        }
+
+        boundaries
+    }
+
+    /// Counts the semantic chunks of the input sentence without building them.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The number of chunks `parse` would return, honoring the same options.
+    #[must_use]
+    pub fn chunk_count(&self, sentence: &str) -> usize {
+        let scored = self.scored_text(sentence);
+        if scored.text().is_empty() {
+            return 0;
+        }
+
+        self.scored_boundaries(&scored).len() + 1
+    }
+
+    /// Checks whether there is a boundary before the given character, scoring only that position.
+    ///
+    /// The sentence is read only up to the third character from `char_pos`, and only the three
+    /// characters on either side of the position are kept for scoring, so the cost grows with
+    /// `char_pos` but not with the length of the rest of the sentence. Feature extractors, like
+    /// in `parse_chunked_stream`, see only those six characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `char_pos` - The character position to check.
+    ///
+    /// # Returns
+    ///
+    /// `true` if `char_pos` is one of the positions returned by `parse_char_boundaries`.
+    #[must_use]
+    pub fn is_boundary_at(&self, mut sentence: &str, char_pos: usize) -> bool {
+        if char_pos == 0 {
+            return false;
+        }
+        if self.strip_bom {
+            sentence = sentence.strip_prefix(BYTE_ORDER_MARK).unwrap_or(sentence);
+        }
+
+        // Collects the scored characters around `char_pos`, skipping the dropped zero width spaces
+        let start = char_pos.saturating_sub(LOOKBEHIND);
+        let mut window = String::new();
+        let mut position = 0;
+        let mut forced = false;
+        for c in sentence.chars() {
+            if self.zero_width_space_as_break && c == ZERO_WIDTH_SPACE {
+                forced |= position == char_pos;
+                continue;
+            }
+            if position == char_pos + LOOKAHEAD {
+                break;
+            }
+            if position >= start {
+                window.push(c);
+            }
+            position += 1;
+        }
+
+        position > char_pos && (forced || self.is_boundary_score(self.score_at(&window, char_pos - start)))
+    }
+
+    /// Returns a lazy iterator over the boundaries of the input sentence, in characters.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// An iterator scoring each position only when it is reached.
+    pub fn boundaries<'a>(&'a self, sentence: &'a str) -> Boundaries<'a> {
+        Boundaries::new(self, sentence)
+    }
+
+    /// Parses the input sentence and returns a list of boundaries as UTF-16 code-unit offsets,
+    /// as used by JavaScript strings.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The list of boundary positions in UTF-16 code units.
+    #[must_use]
+    pub fn parse_positions_utf16(&self, sentence: &str) -> Vec<usize> {
+        let offsets = sentence
+            .chars()
+            .scan(0, |offset, c| {
+                let start = *offset;
+                *offset += c.len_utf16();
+                Some(start)
+            })
+            .collect::<Vec<_>>();
+
+        self.text_boundaries(sentence).into_iter().map(|i| offsets[i]).collect()
+    }
+
+    /// Parses the input sentence and returns the chunks paired with the score of the boundary
+    /// preceding each of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The retrieved chunks, with `None` as the score of the first chunk.
+    #[must_use]
+    pub fn parse_segments_with_scores<'a>(&self, sentence: &'a str) -> Vec<(&'a str, Option<i64>)> {
+        if sentence.is_empty() {
+            return Vec::new();
+        }
+
+        let context = ScoringContext::new(sentence);
+        let (boundaries, scores): (Vec<_>, Vec<_>) = (1..context.len())
+            .map(|i| (i, self.score_in_context(&context, i)))
+            .filter(|&(_, score)| self.is_boundary_score(score))
+            .unzip();
+
+        split_at_boundaries(sentence, &boundaries)
+            .into_iter()
+            .zip([None].into_iter().chain(scores.into_iter().map(Some)))
+            .collect()
+    }
+
+    /// Sums the boundary scores of all positions in the input sentence.
+    ///
+    /// Values far from zero mean the model is confident throughout the sentence, either about
+    /// breaking or about not breaking; values near zero indicate uncertainty.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The sum of the scores, or 0 if the sentence has fewer than two characters.
+    #[must_use]
+    pub fn sentence_score(&self, sentence: &str) -> i64 {
+        let context = ScoringContext::new(sentence);
+        (1..context.len()).map(|i| self.score_in_context(&context, i)).sum()
+    }
+
+    /// Computes the boundary score before the character at the given position.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    /// * `i` - The character position to score.
+    ///
+    /// # Returns
+    ///
+    /// The score; a boundary is placed at `i` if it is positive.
+    pub(crate) fn score_at(&self, sentence: &str, i: usize) -> i64 {
+        self.score_in_context(&ScoringContext::new(sentence), i)
+    }
+
+    /// Checks whether a score makes a boundary, using the score normalizer if one is set.
+    pub(crate) fn is_boundary_score(&self, score: i64) -> bool {
+        self.score_normalizer
+            .as_ref()
+            .map_or(score > 0, |normalizer| normalizer(score))
+    }
+
+    /// Prepares a sentence for scoring many of its positions with `score_in_context`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The sentence with its character offsets cached.
+    pub fn score_with_context<'a>(&self, sentence: &'a str) -> ScoringContext<'a> {
+        ScoringContext::new(sentence)
+    }
+
+    /// Computes the boundary score before the character at the given position of a prepared
+    /// sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - A sentence prepared by `score_with_context`.
+    /// * `i` - The character position to score.
+    ///
+    /// # Returns
+    ///
+    /// The score; a boundary is placed at `i` if it is positive.
+    #[must_use]
+    pub fn score_in_context(&self, context: &ScoringContext<'_>, i: usize) -> i64 {
+        let mut score = self.base_score;
+
+        for (key, value) in feature_substrings(context, i) {
+            let feature_score = self.get_score(key, value);
+            #[cfg(feature = "log")]
+            if cfg!(debug_assertions) && log::log_enabled!(log::Level::Trace) {
+                log::trace!("{} {:?} at {} scored {}", key, value, i, feature_score);
+            }
+            score += feature_score;
+        }
+
+        for extractor in &self.extractors {
+            score += extractor.extra_score(context.sentence(), i);
+        }
+
+        score
+    }
+
+    /// Gets the score for a given key and value from the model.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The model feature group key.
+    /// * `value` - The specific substring to score.
+    ///
+    /// # Returns
+    ///
+    /// The score value or 0 if not found.
+    fn get_score(&self, key: &str, value: &str) -> i64 {
+        self.model.get(key, value).unwrap_or(0)
+    }
+
+    /// Initializes all compiled-in default models on a background thread.
+    ///
+    /// The default models are built lazily on first use, which makes the first parse noticeably
+    /// slower. Calling this during startup moves that cost off the request path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the target does not support threads, as is the case for `wasm32-wasip1`.
+    /// There, call `preload_models` instead.
+    ///
+    /// # Returns
+    ///
+    /// A handle to the thread performing the initialization.
+    pub fn warm_up() -> JoinHandle<()> {
+        thread::spawn(Self::preload_models)
+    }
+
+    /// Initializes all compiled-in default models on the current thread, blocking until done.
+    ///
+    /// Unlike `warm_up`, this also works on targets without threads.
+    pub fn preload_models() {
+        #[cfg(feature = "ja")]
+        LazyLock::force(&JA_MODEL);
+        #[cfg(feature = "zh-hans")]
+        LazyLock::force(&ZH_HANS_MODEL);
+        #[cfg(feature = "zh-hant")]
+        LazyLock::force(&ZH_HANT_MODEL);
+        #[cfg(feature = "th")]
+        LazyLock::force(&TH_MODEL);
+    }
+
+    /// Checks whether all compiled-in default models have been initialized.
+    ///
+    /// # Returns
+    ///
+    /// `true` if every enabled default model is loaded, or if no default model is enabled.
+    pub fn is_model_loaded() -> bool {
+        let loaded: &[bool] = &[
+            #[cfg(feature = "ja")]
+            LazyLock::get(&JA_MODEL).is_some(),
+            #[cfg(feature = "zh-hans")]
+            LazyLock::get(&ZH_HANS_MODEL).is_some(),
+            #[cfg(feature = "zh-hant")]
+            LazyLock::get(&ZH_HANT_MODEL).is_some(),
+            #[cfg(feature = "th")]
+            LazyLock::get(&TH_MODEL).is_some(),
+        ];
+
+        loaded.iter().all(|&loaded| loaded)
+    }
+
+    /// Lists the languages whose default models are compiled in.
+    ///
+    /// # Returns
+    ///
+    /// The languages enabled by the crate features, in declaration order.
+    pub const fn available_languages() -> &'static [Language] {
+        &[
+            #[cfg(feature = "ja")]
+            Language::Japanese,
+            #[cfg(feature = "zh-hans")]
+            Language::SimplifiedChinese,
+            #[cfg(feature = "zh-hant")]
+            Language::TraditionalChinese,
+            #[cfg(feature = "th")]
+            Language::Thai,
+        ]
+    }
+
+    /// Loads a parser equipped with the default Japanese model.
+    ///
+    /// # Returns
+    ///
+    /// A parser with the default Japanese model.
+    #[cfg(feature = "ja")]
+    pub fn load_default_japanese_parser() -> Self {
+        Self::new(JA_MODEL.to_owned())
+    }
+
+    /// Loads a parser equipped with the default Simplified Chinese model.
+    ///
+    /// # Returns
+    ///
+    /// A parser with the default Simplified Chinese model.
+    #[cfg(feature = "zh-hans")]
+    pub fn load_default_simplified_chinese_parser() -> Self {
+        Self::new(ZH_HANS_MODEL.to_owned())
+    }
+
+    /// Loads a parser equipped with the default Traditional Chinese model.
+    ///
+    /// # Returns
+    ///
+    /// A parser with the default Traditional Chinese model.
+    #[cfg(feature = "zh-hant")]
+    pub fn load_default_traditional_chinese_parser() -> Self {
+        Self::new(ZH_HANT_MODEL.to_owned())
+    }
+
+    /// Loads a parser equipped with the default Thai model.
+    ///
+    /// # Returns
+    ///
+    /// A parser with the default Thai model.
+    #[cfg(feature = "th")]
+    pub fn load_default_thai_parser() -> Self {
+        Self::new(TH_MODEL.to_owned())
+    }
+
+    /// Loads a parser equipped with the default Lao model.
+    ///
+    /// BudouX does not ship a Lao model yet, so this always fails for now; it will return the
+    /// parser once the upstream model exists.
+    ///
+    /// # Returns
+    ///
+    /// A parser with the default Lao model, or `ModelLoadError::NotYetAvailable`.
+    #[cfg(feature = "lo")]
+    pub fn load_default_lao_parser() -> Result<Self, ModelLoadError> {
+        Err(ModelLoadError::NotYetAvailable("lo"))
+    }
+}
+
+impl PartialEq for Parser<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.base_score == other.base_score
+            && self.model.sorted_entries() == other.model.sorted_entries()
+            && self.forced_breaks == other.forced_breaks
+            && self.zero_width_space_as_break == other.zero_width_space_as_break
+            && self.strip_bom == other.strip_bom
+            && self.extractors.len() == other.extractors.len()
+            && self
+                .extractors
+                .iter()
+                .zip(&other.extractors)
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+            && match (&self.score_normalizer, &other.score_normalizer) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            }
+    }
+}
+
+impl Eq for Parser<'_> {}
+
+impl Hash for Parser<'_> {
+    /// Hashes the base score followed by the sorted `(key, subkey, value)` triples of the model,
+    /// so that the result does not depend on the iteration order of the underlying maps.
+    /// Extractors and the score normalizer are hashed by address.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base_score.hash(state);
+        self.model.sorted_entries().hash(state);
+        self.forced_breaks.hash(state);
+        self.zero_width_space_as_break.hash(state);
+        self.strip_bom.hash(state);
+        self.extractors.len().hash(state);
+        for extractor in &self.extractors {
+            Arc::as_ptr(extractor).cast::<()>().hash(state);
+        }
+        self.score_normalizer
+            .as_ref()
+            .map(|normalizer| Arc::as_ptr(normalizer).cast::<()>())
+            .hash(state);
+    }
+}
+
+/// Computes the base score offsetting a total model weight, so that a position is a boundary
+/// when its features weigh more than half of the total.
+pub(crate) fn base_score_of_sum(sum: i64) -> i64 {
+    -((sum + 1) / 2)
+}
+
+/// Returns the largest gap between consecutive positions, starting from 0.
+fn max_span(positions: impl IntoIterator<Item = usize>) -> usize {
+    let mut start = 0;
+    positions
+        .into_iter()
+        .map(|end| {
+            let span = end - start;
+            start = end;
+            span
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Splits the sentence into chunks of `n` characters, the last one possibly shorter.
+fn split_every(sentence: &str, n: usize) -> Vec<&str> {
+    let boundaries = (n..sentence.chars().count()).step_by(n).collect::<Vec<_>>();
+    split_at_boundaries(sentence, &boundaries)
+}
+
+/// Yields the feature group and substring of every feature window around a position.
+///
+/// # Arguments
+///
+/// * `context` - The sentence being scored.
+/// * `i` - The character position being scored.
+pub(crate) fn feature_substrings<'a>(
+    context: &ScoringContext<'a>,
+    i: usize,
+) -> impl Iterator<Item = (&'static str, &'a str)> {
+    FEATURE_WINDOWS.into_iter().map(move |(key, start, end)| {
+        (
+            key,
+            context.substring(i.saturating_add_signed(start), i.saturating_add_signed(end)),
+        )
+    })
+}
+
+/// Escapes the characters with a special meaning in HTML text and attribute values.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Named HTML character references decoded by `decode_html_entities`.
+const HTML_ENTITIES: [(&str, char); 14] = [
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{A0}'),
+    ("ensp", '\u{2002}'),
+    ("emsp", '\u{2003}'),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("hellip", '…'),
+    ("middot", '·'),
+    ("copy", '©'),
+    ("reg", '®'),
+];
+
+/// The longest character reference looked for by `decode_html_entities`, including the `;`.
+const MAX_HTML_REFERENCE_CHARS: usize = 12;
+
+/// Decodes numeric and common named HTML character references, keeping unknown ones as is.
+///
+/// Numeric references must consist of digits only. Like in browsers, those to U+0000, to a
+/// surrogate or past U+10FFFF decode to U+FFFD.
+fn decode_html_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        // References are short, so looking further for the `;` would only make decoding quadratic.
+        let reference = rest[1..]
+            .char_indices()
+            .take(MAX_HTML_REFERENCE_CHARS)
+            .find(|&(_, c)| c == ';')
+            .map(|(end, _)| &rest[1..end + 1]);
+        let c = reference.and_then(|name| match name.strip_prefix('#') {
+            Some(number) => match number.strip_prefix(['x', 'X']) {
+                Some(hex) if hex.bytes().all(|b| b.is_ascii_hexdigit()) => u32::from_str_radix(hex, 16).ok(),
+                None if number.bytes().all(|b| b.is_ascii_digit()) => number.parse().ok(),
+                _ => None,
+            }
+            .map(|code| {
+                char::from_u32(code)
+                    .filter(|&c| c != '\0')
+                    .unwrap_or(char::REPLACEMENT_CHARACTER)
+            }),
+            None => HTML_ENTITIES
+                .iter()
+                .find(|(entity, _)| *entity == name)
+                .map(|&(_, c)| c),
+        });
+
+        match (reference, c) {
+            (Some(name), Some(c)) => {
+                decoded.push(c);
+                rest = &rest[name.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+/// Splits a document at its blank lines into the non-empty paragraphs between them, without the
+/// line breaks at their edges.
+fn split_paragraphs(document: &str) -> Vec<&str> {
+    let mut paragraphs = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in document.split_inclusive('\n') {
+        let content = line
+            .strip_suffix('\n')
+            .map_or(line, |line| line.strip_suffix('\r').unwrap_or(line));
+        if content.is_empty() {
+            if let Some(start) = start.take() {
+                paragraphs.push(&document[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        paragraphs.push(&document[start..end]);
+    }
+
+    paragraphs
+}
+
+/// Returns the backticks or tildes opening or closing a fenced code block on a Markdown line.
+fn code_fence(line: &str) -> Option<&str> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let marker = &line[indent..];
+    let c = marker
+        .chars()
+        .next()
+        .filter(|&c| indent <= 3 && (c == '`' || c == '~'))?;
+    let len = marker.len() - marker.trim_start_matches(c).len();
+    (len >= 3).then(|| &marker[..len])
+}
+
+/// Returns the byte length of the inline code span opened by `run` backticks at the start of
+/// `text`, or `None` if no run of exactly as many backticks closes it.
+fn code_span_len(text: &str, run: usize) -> Option<usize> {
+    let mut i = run;
+    while let Some(start) = text[i..].find('`') {
+        let start = i + start;
+        let len = text[start..].len() - text[start..].trim_start_matches('`').len();
+        if len == run {
+            return Some(start + len);
+        }
+        i = start + len;
+    }
+    None
+}
+
+/// Returns the byte length of the Markdown link whose `[` is at byte `open` of `text`, including
+/// its `(destination)`, or `None` if the text does not start with a link.
+fn link_len(text: &str, open: usize) -> Option<usize> {
+    let label_end = open + matching_bracket_len(&text[open..], '[', ']')?;
+    let destination = &text[label_end..];
+    if !destination.starts_with('(') {
+        return None;
+    }
+    Some(label_end + matching_bracket_len(destination, '(', ')')?)
+}
+
+/// Returns the byte length of the bracketed text at the start of `text` up to and including the
+/// bracket closing it, skipping nested pairs and backslash escapes.
+fn matching_bracket_len(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = text.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            c if c == open => depth += 1,
+            c if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + c.len_utf8());
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A sentence as scored by the methods honoring `with_zero_width_space_as_break` and
+/// `with_bom_strip`, created by `Parser::scored_text`.
+enum ScoredText<'a> {
+    /// The sentence without a stripped byte order mark, which has no other characters to drop
+    Slice(&'a str),
+    /// The sentence with its zero width spaces dropped
+    Stripped {
+        /// Original sentence
+        sentence: &'a str,
+        /// Remaining characters, which are scored
+        text: String,
+        /// Byte range in `sentence` of every character of `text`
+        ranges: Vec<Range<usize>>,
+        /// Positions in `text` where zero width spaces were dropped
+        forced: Vec<usize>,
+    },
+}
+
+impl<'a> ScoredText<'a> {
+    /// Returns the text to score.
+    fn text(&self) -> &str {
+        match self {
+            ScoredText::Slice(text) => text,
+            ScoredText::Stripped { text, .. } => text,
+        }
+    }
+
+    /// Splits the sentence at the given positions of the scored text, leaving out the dropped
+    /// characters.
+    fn split(&self, boundaries: &[usize]) -> Vec<&'a str> {
+        match self {
+            ScoredText::Slice("") => Vec::new(),
+            ScoredText::Slice(text) => split_at_boundaries(text, boundaries),
+            ScoredText::Stripped { sentence, ranges, .. } => {
+                let mut chunks = Vec::new();
+                let mut start = 0;
+                for end in boundaries.iter().copied().chain([ranges.len()]) {
+                    if start < end {
+                        chunks.push(&sentence[ranges[start].start..ranges[end - 1].end]);
+                    }
+                    start = end;
+                }
+                chunks
+            }
+        }
+    }
+}
+
+/// Splits the sentence into chunks at the given character positions.
+pub(crate) fn split_at_boundaries<'a>(sentence: &'a str, boundaries: &[usize]) -> Vec<&'a str> {
+    let mut result = Vec::new();
+    let mut start = 0;
+
+    for &boundary in boundaries {
+        result.push(sentence.substring(start, boundary));
+        start = boundary;
+    }
+    result.push(sentence.substring(start, sentence.len()));
+
+    result
+}
+
+trait Substring {
+    fn substring(&self, start: usize, end: usize) -> &str;
+}
+
+impl Substring for str {
+    #[inline]
+    fn substring(&self, start: usize, end: usize) -> &str {
+        let char_indices = self.char_indices().collect::<Vec<_>>();
+        let start_byte = char_indices.get(start).map(|(byte, _)| *byte).unwrap_or(self.len());
+        let end_byte = char_indices.get(end).map(|(byte, _)| *byte).unwrap_or(self.len());
+
+        &self[start_byte..end_byte]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+    use std::collections::HashSet;
+
+    const TEST_SENTENCE: &str = "abcdeabcd";
+
+    /// A model looking at both sides of the scored position: a boundary before `b` (`UW4`),
+    /// strengthened by a `d` two characters later (`UW6`) and cancelled after `dea` (`TW1`).
+    /// `TEST_SENTENCE` is split as `a|bcdeabcd`.
+    fn context_model() -> Model {
+        model_from_tuples! {
+            "UW4" => [("b", 10000)],
+            "UW6" => [("d", 6000)],
+            "TW1" => [("dea", -16000)],
+        }
+    }
+
+    #[test]
+    fn should_separate_if_a_strong_feature_item_supports() {
+        let model = model_from_tuples! { "UW4" => [("a", 10000)] };
+
+        let parser = Parser::new(model);
+        let result = parser.parse(TEST_SENTENCE);
+
+        assert_eq!(result, vec!["abcde", "abcd"]);
+    }
+
+    #[test]
+    fn should_separate_even_if_it_makes_a_phrase_of_one_character() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+
+        let parser = Parser::new(model);
+        let result = parser.parse(TEST_SENTENCE);
+
+        assert_eq!(result, vec!["a", "bcdea", "bcd"]);
+    }
+
+    #[test]
+    fn should_return_an_empty_list_when_the_input_is_a_blank_string() {
+        let model = Model::new();
+        let parser = Parser::new(model);
+        let result = parser.parse("");
+
+        assert_eq!(result, Vec::<String>::new());
+    }
 
-        assert_eq!(result, vec!["a", "bcdea", "bcd"]);
+    #[test]
+    fn should_hash_parsers_with_the_same_model_equally() {
+        let model = model_from_tuples! { "UW4" => [("a", 10000), ("b", 5000)] };
+
+        let mut other = model.clone();
+        other.get_mut("UW4").unwrap().insert("c".to_string(), 1);
+
+        let mut set = HashSet::new();
+        set.insert(Parser::new(model.clone()));
+        set.insert(Parser::new(model));
+        set.insert(Parser::new(other));
+
+        assert_eq!(set.len(), 2);
     }
 
     #[test]
-    fn should_return_an_empty_list_when_the_input_is_a_blank_string() {
-        let model = HashMap::new();
+    fn should_always_separate_after_forced_break_punctuation() {
+        let parser = Parser::new(Model::new());
+
+        assert_eq!(parser.parse_punct("あい。うえ、お。"), vec!["あい。", "うえ、", "お。"]);
+
+        let parser = parser.with_forced_breaks(['c']);
+        assert_eq!(parser.parse_punct(TEST_SENTENCE), vec!["abc", "deabc", "d"]);
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "wasi", ignore = "threads are not supported")]
+    fn should_report_models_as_loaded_after_warm_up() {
+        Parser::warm_up().join().unwrap();
+
+        assert!(Parser::is_model_loaded());
+    }
+
+    #[test]
+    fn should_write_chunks_as_conll_tokens() {
+        let model = model_from_tuples! { "UW4" => [("a", 10000)] };
+
         let parser = Parser::new(model);
-        let result = parser.parse("");
+        let mut output = Vec::new();
+        parser.write_conll(TEST_SENTENCE, &mut output).unwrap();
 
-        assert_eq!(result, Vec::<String>::new());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "# text = abcdeabcd\n\
+             1\tabcde\t_\t_\t_\t_\t_\t_\t_\tSpaceAfter=No\n\
+             2\tabcd\t_\t_\t_\t_\t_\t_\t_\t_\n\n"
+        );
+    }
+
+    #[test]
+    fn should_reject_conll_sentences_with_tabs_or_line_breaks() {
+        let parser = Parser::new(Model::new());
+        let mut output = Vec::new();
+
+        for sentence in ["a\tb\nab", "a\tb", "a\nb", "a\r\nb"] {
+            let error = parser.write_conll(sentence, &mut output).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput, "{sentence:?}");
+        }
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn should_write_nothing_for_an_empty_conll_sentence() {
+        let parser = Parser::new(Model::new());
+        let mut output = Vec::new();
+        parser.write_conll("", &mut output).unwrap();
+
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn should_join_chunks_with_the_separator() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+
+        let parser = Parser::new(model);
+
+        assert_eq!(parser.parse_and_join(TEST_SENTENCE, "<wbr>"), "a<wbr>bcdea<wbr>bcd");
+        assert_eq!(parser.parse_and_join("", "<wbr>"), "");
+    }
+
+    #[test]
+    fn should_measure_the_longest_chunk() {
+        let model = model_from_tuples! { "UW4" => [("う", 10000)] };
+
+        let parser = Parser::new(model);
+
+        assert_eq!(parser.parse_max_chunk_chars("あいうえおかき"), 5);
+        assert_eq!(parser.parse_max_chunk_bytes("あいうえおかき"), 15);
+        assert_eq!(parser.parse_max_chunk_chars(""), 0);
+        assert_eq!(parser.parse_max_chunk_bytes(""), 0);
+    }
+
+    #[test]
+    fn should_pair_chunks_with_the_score_of_the_preceding_boundary() {
+        let parser = Parser::new(context_model());
+        let result = parser.parse_segments_with_scores("abcdab");
+
+        assert_eq!(result, vec![("a", None), ("bcda", Some(16000)), ("b", Some(10000))]);
+        assert!(parser.parse_segments_with_scores("").is_empty());
+    }
+
+    #[test]
+    fn should_use_the_new_model_after_reset() {
+        let mut parser = Parser::new(model_from_tuples! { "UW4" => [("a", 10000)] });
+        parser.reset_with_model(model_from_tuples! { "UW4" => [("b", 10000)] });
+
+        assert!(parser == Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] }));
+        assert_eq!(parser.parse(TEST_SENTENCE), vec!["a", "bcdea", "bcd"]);
+    }
+
+    #[test]
+    fn should_count_supplementary_characters_as_two_utf16_units() {
+        let model = model_from_tuples! { "UW3" => [("𠮷", 10000)], "UW4" => [("b", 10000)], "UW1" => [("z", -20000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(parser.parse_positions_utf16("𠮷abあb"), vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn should_return_boundaries_in_chars_and_bytes() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)], "UW3" => [("い", 10000)], "UW1" => [("z", -20000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(
+            parser.parse_char_boundaries("あbいう"),
+            vec![CharIndex(1), CharIndex(3)]
+        );
+        assert_eq!(
+            parser.parse_byte_boundaries("あbいう"),
+            vec![ByteIndex(3), ByteIndex(7)]
+        );
+        #[allow(deprecated)]
+        let boundaries = parser.parse_boundaries("あbいう");
+        assert_eq!(boundaries, vec![1, 3]);
+    }
+
+    #[test]
+    fn should_add_scores_from_extra_features() {
+        struct BreakBeforeD;
+
+        impl FeatureExtractor for BreakBeforeD {
+            fn extra_score(&self, sentence: &str, position: usize) -> i64 {
+                if sentence.chars().nth(position) == Some('d') {
+                    1
+                } else {
+                    0
+                }
+            }
+        }
+
+        let parser = Parser::new(Model::new()).with_extra_features(Box::new(BreakBeforeD));
+
+        assert_eq!(parser.parse(TEST_SENTENCE), vec!["abc", "deabc", "d"]);
+    }
+
+    #[test]
+    fn should_add_scores_from_a_closure() {
+        let parser = Parser::new(Model::new()).with_fn_extractor(|_, position| if position % 3 == 0 { 1 } else { -1 });
+
+        assert_eq!(parser.parse(TEST_SENTENCE), vec!["abc", "dea", "bcd"]);
+    }
+
+    #[test]
+    fn should_split_oversized_chunks_with_the_fallback() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+
+        assert_eq!(
+            parser.parse_rebalanced(TEST_SENTENCE, 3, SplitStrategy::EveryN(3)),
+            vec!["a", "bcd", "ea", "bcd"]
+        );
+        assert_eq!(
+            parser.parse_rebalanced(TEST_SENTENCE, 3, SplitStrategy::BigramSplit),
+            vec!["a", "bc", "de", "a", "bcd"]
+        );
+        assert_eq!(
+            parser.parse_rebalanced("xあい、うえ", 3, SplitStrategy::AtPunctuation),
+            vec!["xあい、", "うえ"]
+        );
+    }
+
+    #[test]
+    fn should_parse_only_the_given_range() {
+        let parser = Parser::new(model_from_tuples! {
+            "UW4" => [("b", 10000)],
+            "UW3" => [("e", 10000)],
+            "TW1" => [("xyz", -10000)],
+        });
+
+        assert_eq!(parser.parse(TEST_SENTENCE), vec!["a", "bcde", "a", "bcd"]);
+        assert_eq!(parser.parse_between(TEST_SENTENCE, 2, 7), vec!["cde", "a", "b"]);
+        assert!(parser.parse_between(TEST_SENTENCE, 3, 3).is_empty());
+    }
+
+    #[test]
+    fn should_sum_the_scores_of_all_positions() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)], "TW1" => [("xyz", -10000)] });
+
+        assert_eq!(parser.sentence_score("abcb"), 20000);
+        assert_eq!(parser.sentence_score("a"), 0);
+    }
+
+    #[test]
+    fn should_normalize_whitespace_before_parsing() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+
+        assert_eq!(parser.parse_normalized("  a \t\n bc  "), vec!["a ", "bc"]);
+        assert!(parser.parse_normalized(" \n ").is_empty());
+    }
+
+    #[test]
+    fn should_parse_each_window() {
+        let parser = Parser::new(context_model());
+
+        // The window starting at `e` breaks before `b`, as it does not see the `d` cancelling that.
+        assert_eq!(
+            parser.parse_sliding_window(TEST_SENTENCE, 4, 2),
+            vec![vec!["a", "bcd"], vec!["cdea"], vec!["ea", "bc"], vec!["bcd"]]
+        );
+        assert_eq!(parser.parse_sliding_window("ab", 4, 3), vec![vec!["a", "b"]]);
+        assert!(parser.parse_sliding_window("", 4, 3).is_empty());
+    }
+
+    #[test]
+    fn should_return_the_chunk_containing_the_position() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+
+        assert_eq!(parser.parse_chunk_at(TEST_SENTENCE, 0), Some("a"));
+        assert_eq!(parser.parse_chunk_at(TEST_SENTENCE, 1), Some("bcdea"));
+        assert_eq!(parser.parse_chunk_at(TEST_SENTENCE, 5), Some("bcdea"));
+        assert_eq!(parser.parse_chunk_at(TEST_SENTENCE, 8), Some("bcd"));
+        assert_eq!(parser.parse_chunk_at(TEST_SENTENCE, 9), None);
+    }
+
+    #[test]
+    fn should_parse_with_a_borrowed_model_like_an_owned_one() {
+        let model = model_from_tuples! { "UW4" => [("a", 10000)] };
+
+        let borrowed = Parser::with_ref_model(ModelRef::from(&model));
+        assert_eq!(borrowed.parse(TEST_SENTENCE), vec!["abcde", "abcd"]);
+        assert!(borrowed == Parser::new(model.clone()));
+    }
+
+    #[test]
+    fn should_score_the_sentence_start_with_the_preceding_context() {
+        let model = model_from_tuples! { "UW2" => [("x", 10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(parser.parse("abc"), vec!["abc"]);
+        assert_eq!(parser.parse_with_context("wx", "abc"), vec!["a", "bc"]);
+        assert_eq!(parser.parse_with_context("xwww", "abc"), vec!["abc"]);
+    }
+
+    #[test]
+    #[cfg(feature = "lo")]
+    fn should_report_the_lao_model_as_not_yet_available() {
+        assert!(matches!(
+            Parser::load_default_lao_parser(),
+            Err(ModelLoadError::NotYetAvailable("lo"))
+        ));
+    }
+
+    #[test]
+    fn should_count_chunks_like_parse() {
+        let parser = Parser::new(context_model());
+
+        for sentence in [TEST_SENTENCE, "abab", "cdeab", "xbad", "b"] {
+            assert_eq!(parser.chunk_count(sentence), parser.parse(sentence).len());
+        }
+        assert_eq!(parser.chunk_count(TEST_SENTENCE), 2);
+        assert_eq!(parser.chunk_count(""), 0);
+    }
+
+    #[test]
+    fn should_query_single_boundaries() {
+        let parser = Parser::new(context_model());
+
+        for sentence in [TEST_SENTENCE, "abab", "cdeab", "xbad"] {
+            let boundaries = parser.parse_char_boundaries(sentence);
+            for i in 0..=sentence.len() {
+                assert_eq!(parser.is_boundary_at(sentence, i), boundaries.contains(&CharIndex(i)));
+            }
+        }
+        assert!(parser.is_boundary_at(TEST_SENTENCE, 1));
+        assert!(!parser.is_boundary_at(TEST_SENTENCE, 6));
+    }
+
+    #[test]
+    fn should_query_single_boundaries_with_dropped_characters() {
+        let parser = Parser::new(context_model())
+            .with_bom_strip(true)
+            .with_zero_width_space_as_break(true);
+
+        for sentence in ["\u{FEFF}abcde\u{200B}abcdxbad", "cdeab\u{200B}\u{200B}xbadabab\u{200B}"] {
+            let boundaries = parser.parse_char_boundaries(sentence);
+            for i in 0..=sentence.chars().count() {
+                assert_eq!(
+                    parser.is_boundary_at(sentence, i),
+                    boundaries.contains(&CharIndex(i)),
+                    "{sentence:?} at {i}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_keep_only_boundaries_accepted_by_the_filter() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(
+            parser.parse_with_position_filter(TEST_SENTENCE, |_, score| score > 0),
+            parser.parse(TEST_SENTENCE)
+        );
+        assert_eq!(
+            parser.parse_with_position_filter(TEST_SENTENCE, |i, score| score > 0 && i > 1),
+            vec!["abcdea", "bcd"]
+        );
+        assert_eq!(
+            parser.parse_with_position_filter(TEST_SENTENCE, |i, _| i == 3),
+            vec!["abc", "deabcd"]
+        );
+    }
+
+    #[test]
+    fn should_report_models_as_loaded_after_preloading() {
+        Parser::preload_models();
+
+        assert!(Parser::is_model_loaded());
+    }
+
+    #[test]
+    fn should_split_sentences_before_parsing() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(
+            parser.parse_sentence_aware("abc。cb！a"),
+            vec![vec!["a", "bc。"], vec!["c", "b！"], vec!["a"]]
+        );
+        assert!(parser.parse_sentence_aware("").is_empty());
+    }
+
+    #[test]
+    fn should_share_one_model_between_parsers() {
+        let model = Arc::new(model_from_tuples! { "UW4" => [("a", 10000)] });
+
+        let first = Parser::with_shared_model(Arc::clone(&model));
+        let second = Parser::with_shared_model(Arc::clone(&model));
+
+        assert_eq!(Arc::strong_count(&model), 3);
+        assert_eq!(first.parse(TEST_SENTENCE), vec!["abcde", "abcd"]);
+        assert!(first == second);
+    }
+
+    #[test]
+    fn should_return_only_the_first_chunk() {
+        let parser = Parser::new(context_model());
+
+        assert_eq!(parser.parse_first_chunk(TEST_SENTENCE), "a");
+        assert_eq!(parser.parse_first_chunk("cdeabab"), "cdeaba");
+        assert_eq!(parser.parse_first_chunk("cde"), "cde");
+        assert_eq!(parser.parse_first_chunk(""), "");
+    }
+
+    #[test]
+    fn should_return_only_the_last_chunk() {
+        let parser = Parser::new(context_model());
+
+        assert_eq!(parser.parse_last_chunk(TEST_SENTENCE), "bcdeabcd");
+        assert_eq!(parser.parse_last_chunk("abab"), "b");
+        assert_eq!(parser.parse_last_chunk("cde"), "cde");
+        assert_eq!(parser.parse_last_chunk(""), "");
+    }
+
+    #[test]
+    fn should_pass_ascii_input_through_unsplit() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000), ("び", 10000), ("x", -10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(parser.parse_ascii_passthrough(TEST_SENTENCE), vec![TEST_SENTENCE]);
+        assert_eq!(parser.parse_ascii_passthrough("あびあ"), vec!["あ", "びあ"]);
+        assert!(parser.parse_ascii_passthrough("").is_empty());
+    }
+
+    #[test]
+    fn should_return_single_characters_as_one_chunk() {
+        let model = model_from_tuples! {
+            "UW4" => [("a", 10000), ("あ", 10000), ("学", 10000), ("ก", 10000), ("z", -40000)],
+        };
+        let parser = Parser::new(model);
+
+        for sentence in ["a", "あ", "学", "ก"] {
+            assert_eq!(parser.parse(sentence), vec![sentence]);
+        }
+    }
+
+    #[test]
+    fn should_skip_parsing_latin_text() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000), ("び", 10000), ("x", -10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(parser.try_parse(TEST_SENTENCE), None);
+        assert_eq!(parser.try_parse("Crème brûlée"), None);
+        assert_eq!(parser.try_parse("あびあ"), Some(vec!["あ", "びあ"]));
+    }
+
+    #[test]
+    fn should_wrap_chunks_in_escaped_spans() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(
+            parser.parse_to_html_span("a<b", "chunk", false),
+            r#"<span class="chunk">a&lt;</span><span class="chunk">b</span>"#
+        );
+        assert_eq!(
+            parser.parse_to_html_span("ab", "budoux-chunk", true),
+            r#"<span class="budoux-chunk" style="display:inline-block">a</span><span class="budoux-chunk" style="display:inline-block">b</span>"#
+        );
+    }
+
+    #[test]
+    fn should_annotate_chunks_with_ruby_text() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(
+            parser.parse_to_ruby("ab", |chunk| format!("<{}>", chunk.len())),
+            "<ruby><rb>a</rb><rt>&lt;1&gt;</rt></ruby><ruby><rb>b</rb><rt>&lt;1&gt;</rt></ruby>"
+        );
+        assert_eq!(parser.parse_to_ruby("", |_| String::new()), "");
+    }
+
+    #[test]
+    fn should_mark_chunk_boundaries_as_ssml_breaks() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(
+            parser.parse_to_ssml("&b", BreakStrength::Weak),
+            r#"<speak>&amp;<break strength="weak"/>b</speak>"#
+        );
+        assert_eq!(parser.parse_to_ssml("", BreakStrength::Strong), "<speak></speak>");
+        assert_eq!(
+            parser.parse_to_ssml("ab", BreakStrength::XStrong),
+            r#"<speak>a<break strength="x-strong"/>b</speak>"#
+        );
+    }
+
+    #[test]
+    fn should_classify_breaks_with_hard_line_breaks() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000), ("\n", 10000), ("x", -20000)] };
+        let parser = Parser::new(model);
+        let hint = |text, break_class| BreakOpportunity { text, break_class };
+
+        assert_eq!(
+            parser.parse_with_linebreak_hints("ab\nc\u{2028}d"),
+            vec![
+                hint("a", BreakClass::Allowed),
+                hint("b", BreakClass::Prohibited),
+                hint("\n", BreakClass::Mandatory),
+                hint("c\u{2028}", BreakClass::Mandatory),
+                hint("d", BreakClass::Mandatory),
+            ]
+        );
+        assert!(parser.parse_with_linebreak_hints("").is_empty());
+    }
+
+    #[test]
+    fn should_decode_html_entities_before_parsing() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(parser.parse_html_text("a&#98;&lt;&#x62;&mdash;"), vec!["a", "b<", "b—"]);
+        assert_eq!(parser.parse_html_text("a&unknown;b & c"), vec!["a&unknown;", "b & c"]);
+    }
+
+    #[test]
+    fn should_decode_null_and_surrogate_references_as_replacement_characters() {
+        assert_eq!(decode_html_entities("a&#0;b"), "a\u{FFFD}b");
+        assert_eq!(decode_html_entities("&#xD800;&#57343;"), "\u{FFFD}\u{FFFD}");
+        assert_eq!(decode_html_entities("&#x110000;"), "\u{FFFD}");
+    }
+
+    #[test]
+    fn should_keep_signed_numeric_references_as_is() {
+        assert_eq!(decode_html_entities("&#+98;&#-98;&#x+62;"), "&#+98;&#-98;&#x+62;");
+        assert_eq!(decode_html_entities("&#;&#x;"), "&#;&#x;");
+    }
+
+    #[test]
+    fn should_break_at_and_strip_zero_width_spaces() {
+        // Breaks before `bc`, which is only seen if the zero width space is removed first.
+        let model = model_from_tuples! { "BW3" => [("bc", 10000)], "UW1" => [("z", -10000)] };
+        let parser = Parser::new(model.clone()).with_zero_width_space_as_break(true);
+
+        assert_eq!(parser.parse("ab\u{200B}cd"), vec!["a", "b", "cd"]);
+        assert_eq!(parser.parse("\u{200B}a\u{200B}\u{200B}c\u{200B}"), vec!["a", "c"]);
+        assert_eq!(Parser::new(model).parse("ab\u{200B}cd"), vec!["ab\u{200B}cd"]);
+    }
+
+    #[test]
+    fn should_agree_on_zero_width_spaces_across_methods() {
+        let parser = Parser::new(Model::new()).with_zero_width_space_as_break(true);
+        let sentence = "aa\u{200B}aa";
+
+        assert_eq!(parser.parse(sentence), vec!["aa", "aa"]);
+        assert_eq!(parser.chunks(sentence).collect::<Vec<_>>(), vec!["aa", "aa"]);
+        assert_eq!(parser.chunk_count(sentence), 2);
+        assert_eq!(parser.parse_char_boundaries(sentence), vec![CharIndex(2)]);
+        assert!(parser.is_boundary_at(sentence, 2));
+        assert_eq!(parser.chunk_count("\u{200B}"), 0);
+        assert_eq!(parser.chunks("\u{200B}").count(), 0);
+    }
+
+    #[test]
+    fn should_keep_the_paragraph_structure_of_documents() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model);
+
+        assert_eq!(
+            parser.parse_document("ab。ab\n\n\n\nab"),
+            vec![
+                Paragraph {
+                    sentences: vec![vec!["a", "b。"], vec!["a", "b"]],
+                },
+                Paragraph {
+                    sentences: vec![vec!["a", "b"]],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_split_paragraphs_at_any_run_of_line_breaks() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model);
+        let expected = vec![
+            Paragraph {
+                sentences: vec![vec!["a", "b"]],
+            };
+            2
+        ];
+
+        assert_eq!(parser.parse_document("ab\n\n\nab"), expected);
+        assert_eq!(parser.parse_document("ab\r\n\r\nab"), expected);
+        assert_eq!(parser.parse_document("\r\nab\r\n\r\n\r\nab\r\n"), expected);
+    }
+
+    #[test]
+    fn should_list_the_compiled_in_languages() {
+        let languages = Parser::available_languages();
+        let enabled = [
+            cfg!(feature = "ja"),
+            cfg!(feature = "zh-hans"),
+            cfg!(feature = "zh-hant"),
+            cfg!(feature = "th"),
+        ];
+
+        assert_eq!(languages.len(), enabled.iter().filter(|&&enabled| enabled).count());
+        #[cfg(feature = "th")]
+        assert_eq!(languages.last(), Some(&Language::Thai));
+    }
+
+    #[test]
+    fn should_insert_wbr_only_in_markdown_prose() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+        let markdown = "abab `ab` [ab](b(b)) ![ab](b) ab\r\n```rust\nabab\n```\n\\`ab ``a`b``\n";
+
+        assert_eq!(
+            parser.parse_markdown(markdown),
+            "a<wbr>ba<wbr>b `ab` [ab](b(b)) ![ab](b) a<wbr>b\r\n```rust\nabab\n```\n\\`a<wbr>b ``a`b``\n"
+        );
+        assert_eq!(parser.parse_markdown("[ab] ab"), "[a<wbr>b] a<wbr>b");
+    }
+
+    #[test]
+    fn should_not_break_inside_skip_ranges() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+
+        assert_eq!(
+            parser.parse_with_skip_ranges("ababab", &[1..4, 5..6]),
+            vec!["a", "baba", "b"]
+        );
+        assert_eq!(parser.parse_with_skip_ranges("ababab", &[0..3, 2..6]), vec!["ababab"]);
+        assert_eq!(parser.parse_with_skip_ranges("ababab", &[]), parser.parse("ababab"));
+        assert!(parser.parse_with_skip_ranges("", &[0..1, 1..2]).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn should_not_break_inside_regex_matches() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+        let pattern = regex::Regex::new("bab").unwrap();
+
+        assert_eq!(
+            parser.parse_with_exclusion_regex("abababab", &pattern),
+            vec!["a", "baba", "bab"]
+        );
+    }
+
+    #[test]
+    fn should_break_where_the_score_normalizer_decides() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000), ("c", 6000), ("x", -16000)] };
+        let parser = Parser::new(model.clone()).with_score_normalizer(|score| score > 8000);
+
+        assert_eq!(Parser::new(model).parse("abcab"), vec!["a", "b", "ca", "b"]);
+        assert_eq!(parser.parse("abcab"), vec!["a", "bca", "b"]);
+        assert_eq!(parser.parse_char_boundaries("abcab"), vec![CharIndex(1), CharIndex(4)]);
+        assert!(!parser.is_boundary_at("abcab", 2));
+    }
+
+    #[test]
+    fn should_parse_bom_prefix_like_the_bare_sentence() {
+        let model = model_from_tuples! { "UW4" => [("学", 10000)] };
+        let parser = Parser::new(model.clone()).with_bom_strip(true);
+
+        assert_eq!(parser.parse("\u{FEFF}私は学生です"), vec!["私は", "学生です"]);
+        assert_eq!(parser.parse("\u{FEFF}私は学生です"), parser.parse("私は学生です"));
+        assert!(parser.parse("\u{FEFF}").is_empty());
+        assert_eq!(parser.chunk_count("\u{FEFF}"), 0);
+        assert_eq!(parser.chunks("\u{FEFF}abab").collect::<Vec<_>>(), vec!["abab"]);
+        assert_eq!(
+            parser.chunks("\u{FEFF}私は学生です").collect::<Vec<_>>(),
+            vec!["私は", "学生です"]
+        );
+        assert_eq!(parser.parse_char_boundaries("\u{FEFF}私は学生です"), vec![CharIndex(2)]);
+        assert_eq!(
+            Parser::new(model).parse("\u{FEFF}私は学生です"),
+            vec!["\u{FEFF}私は", "学生です"]
+        );
+    }
+
+    #[test]
+    fn should_tabulate_chunks_with_their_character_ranges() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+
+        assert_eq!(
+            parser.parse_table(&["abab", "", "学b"]),
+            vec![
+                vec![(0, 1, "a"), (1, 3, "ba"), (3, 4, "b")],
+                vec![],
+                vec![(0, 1, "学"), (1, 2, "b")],
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_only_the_highest_scoring_boundaries() {
+        let parser = Parser::new(model_from_tuples! {
+            "UW4" => [("b", 10000), ("c", 20000), ("x", -30000)],
+        });
+
+        assert_eq!(parser.parse_max_boundaries("abcab", 2), vec!["a", "b", "cab"]);
+        assert_eq!(parser.parse_max_boundaries("abcab", 1), vec!["ab", "cab"]);
+        assert_eq!(parser.parse_max_boundaries("abcab", 0), vec!["abcab"]);
+        assert_eq!(parser.parse_max_boundaries("abcab", 5), parser.parse("abcab"));
+        assert!(parser.parse_max_boundaries("", 1).is_empty());
+    }
+
+    #[test]
+    fn should_pick_the_most_even_boundaries() {
+        let parser = Parser::new(model_from_tuples! { "UW4" => [("b", 10000)] });
+
+        assert_eq!(parser.parse_evenly("abababab", 2), vec!["aba", "babab"]);
+        assert_eq!(parser.parse_evenly("abababab", 3), vec!["aba", "ba", "bab"]);
+        assert_eq!(parser.parse_evenly("abababab", 10), parser.parse("abababab"));
+        assert_eq!(parser.parse_evenly("abababab", 0), vec!["abababab"]);
+        assert!(parser.parse_evenly("", 2).is_empty());
+    }
+
+    #[test]
+    fn should_compare_extractors_and_normalizers_by_identity() {
+        let with_extractor = || Parser::new(Model::new()).with_fn_extractor(|_, _| 1);
+        let with_normalizer = || Parser::new(Model::new()).with_score_normalizer(|score| score > 0);
+
+        let parser = with_extractor();
+        assert!(parser == parser);
+        assert!(with_extractor() != with_extractor());
+        assert!(with_extractor() != Parser::new(Model::new()));
+        assert!(with_normalizer() != with_normalizer());
+        assert!(with_normalizer() != Parser::new(Model::new()));
+
+        let mut set = HashSet::new();
+        set.insert(with_extractor());
+        set.insert(with_extractor());
+        assert_eq!(set.len(), 2);
     }
 }
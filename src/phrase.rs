@@ -0,0 +1,62 @@
+use crate::Parser;
+use crate::parser::SENTENCE_DELIMITERS;
+
+/// An adapter that splits text into sentences at `。`, `！` and `？` and each sentence into
+/// semantic chunks.
+pub struct PhraseBreaker<'m> {
+    /// The parser chunking each sentence
+    parser: Parser<'m>,
+}
+
+impl<'m> PhraseBreaker<'m> {
+    /// Constructs a phrase breaker chunking sentences with a parser.
+    ///
+    /// # Arguments
+    ///
+    /// * `parser` - The parser chunking each sentence.
+    pub fn new(parser: Parser<'m>) -> Self {
+        PhraseBreaker { parser }
+    }
+
+    /// Splits the input text into sentences and chunks, one sentence at a time.
+    ///
+    /// Each delimiter stays attached to the end of the sentence it closes, as in
+    /// `Parser::parse_sentence_aware`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - An input text.
+    ///
+    /// # Returns
+    ///
+    /// An iterator over `(sentence_idx, chunk_idx, chunk_text)` tuples in text order.
+    pub fn phrases<'a>(&'a self, text: &'a str) -> impl Iterator<Item = (usize, usize, &'a str)> + 'a {
+        text.split_inclusive(SENTENCE_DELIMITERS)
+            .enumerate()
+            .flat_map(move |(sentence_idx, sentence)| {
+                self.parser
+                    .parse(sentence)
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(chunk_idx, chunk)| (sentence_idx, chunk_idx, chunk))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+
+    #[test]
+    fn should_number_chunks_within_sentences() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let breaker = PhraseBreaker::new(Parser::new(model));
+
+        assert_eq!(
+            breaker.phrases("ab。ab").collect::<Vec<_>>(),
+            vec![(0, 0, "a"), (0, 1, "b。"), (1, 0, "a"), (1, 1, "b")]
+        );
+        assert_eq!(breaker.phrases("").count(), 0);
+    }
+}
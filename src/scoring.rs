@@ -0,0 +1,76 @@
+use crate::ScoringContext;
+use crate::models::Model;
+use crate::parser::{base_score_of_sum, feature_substrings};
+
+pub use crate::parser::FEATURE_WINDOWS;
+
+/// Computes the base score of a model, the offset added to the feature scores of every position.
+///
+/// # Arguments
+///
+/// * `model` - A BudouX model.
+///
+/// # Returns
+///
+/// Minus half of the total weight of the model, rounded away from zero.
+pub fn base_score(model: &Model) -> i64 {
+    base_score_of_sum(model.entries().map(|(_, _, value)| value).sum())
+}
+
+/// Looks up the score of one feature.
+///
+/// # Arguments
+///
+/// * `model` - A BudouX model.
+/// * `group` - The feature group, one of the keys of `FEATURE_WINDOWS`.
+/// * `substring` - The substring covered by the feature window.
+///
+/// # Returns
+///
+/// The weight of the feature, or 0 if the model does not have it.
+pub fn feature_score(model: &Model, group: &str, substring: &str) -> i64 {
+    model.find_entry(group, substring).unwrap_or(0)
+}
+
+/// Computes the boundary score of every position between two characters of a sentence.
+///
+/// `Parser::parse` places a boundary wherever the score is positive; other decision rules can be
+/// built on the same scores.
+///
+/// # Arguments
+///
+/// * `model` - A BudouX model.
+/// * `sentence` - An input sentence.
+///
+/// # Returns
+///
+/// The scores, where `scores[i - 1]` is the score of a boundary before character `i`.
+pub fn compute_scores(model: &Model, sentence: &str) -> Vec<i64> {
+    let base_score = base_score(model);
+    let context = ScoringContext::new(sentence);
+    (1..context.len())
+        .map(|i| {
+            feature_substrings(&context, i)
+                .map(|(group, substring)| feature_score(model, group, substring))
+                .sum::<i64>()
+                + base_score
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+
+    #[test]
+    fn should_compute_the_scores_behind_parse() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)], "BW2" => [("cd", 2000)] };
+
+        assert_eq!(base_score(&model), -6000);
+        assert_eq!(feature_score(&model, "UW4", "b"), 10000);
+        assert_eq!(feature_score(&model, "UW4", "c"), 0);
+        assert_eq!(compute_scores(&model, "abcd"), vec![4000, -6000, -4000]);
+        assert!(compute_scores(&model, "a").is_empty());
+    }
+}
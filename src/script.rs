@@ -0,0 +1,150 @@
+/// The dominant writing system of a text, as guessed by `sentence_script`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script {
+    /// Kana, possibly mixed with Han characters
+    Japanese,
+    /// Han characters in their simplified forms
+    ChineseSimplified,
+    /// Han characters in their traditional forms
+    ChineseTraditional,
+    /// Thai script
+    Thai,
+    /// Latin script, including ASCII
+    Latin,
+    /// No script makes up more than half of the letters
+    Mixed,
+    /// Letters of a script not listed above
+    Unknown,
+}
+
+/// Common characters that only exist in simplified Chinese.
+const SIMPLIFIED_ONLY: &str = "这们来说时会对发国过没还经进问样见现点从学长实开关东书车买门马鸟为与";
+
+/// The traditional counterparts of `SIMPLIFIED_ONLY`.
+const TRADITIONAL_ONLY: &str = "這們來說時會對發國過沒還經進問樣見現點從學長實開關東書車買門馬鳥為與";
+
+/// Guesses the dominant script of a text by counting the Unicode blocks of its letters.
+///
+/// Digits, punctuation and whitespace are ignored. Japanese is told apart from Chinese by the
+/// presence of kana, and simplified from traditional Chinese by a small list of characters with
+/// distinct forms, so short texts may be misclassified.
+///
+/// # Arguments
+///
+/// * `text` - An input text.
+///
+/// # Returns
+///
+/// The dominant script, or `None` if the text contains no letters.
+pub fn sentence_script(text: &str) -> Option<Script> {
+    let (mut kana, mut han, mut thai, mut latin, mut other) = (0, 0, 0, 0, 0);
+    let (mut simplified, mut traditional) = (0, 0);
+
+    for c in text.chars() {
+        match block(c) {
+            Some(Block::Kana) => kana += 1,
+            Some(Block::Han) => {
+                han += 1;
+                if SIMPLIFIED_ONLY.contains(c) {
+                    simplified += 1;
+                } else if TRADITIONAL_ONLY.contains(c) {
+                    traditional += 1;
+                }
+            }
+            Some(Block::Thai) => thai += 1,
+            Some(Block::Latin) => latin += 1,
+            Some(Block::Other) => other += 1,
+            None => {}
+        }
+    }
+
+    let total = kana + han + thai + latin + other;
+    if total == 0 {
+        return None;
+    }
+
+    let script = if (kana + han) * 2 > total {
+        if kana > 0 {
+            Script::Japanese
+        } else if traditional > simplified {
+            Script::ChineseTraditional
+        } else {
+            Script::ChineseSimplified
+        }
+    } else if thai * 2 > total {
+        Script::Thai
+    } else if latin * 2 > total {
+        Script::Latin
+    } else if other * 2 > total {
+        Script::Unknown
+    } else {
+        Script::Mixed
+    };
+
+    Some(script)
+}
+
+/// The groups of Unicode blocks told apart by `sentence_script`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Block {
+    Kana,
+    Han,
+    Thai,
+    Latin,
+    Other,
+}
+
+impl Block {
+    /// Checks whether text in both blocks belongs to the same language, treating kana and Han
+    /// characters as one since Japanese mixes them.
+    pub(crate) fn is_compatible(self, other: Block) -> bool {
+        let cjk = |block| matches!(block, Block::Kana | Block::Han);
+        self == other || (cjk(self) && cjk(other))
+    }
+}
+
+/// Returns the block group of a letter, or `None` for digits, punctuation and whitespace.
+pub(crate) fn block(c: char) -> Option<Block> {
+    if !c.is_alphabetic() {
+        return None;
+    }
+
+    let block = match c {
+        '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => Block::Kana,
+        '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' | '\u{20000}'..='\u{2FFFF}' => {
+            Block::Han
+        }
+        '\u{0E00}'..='\u{0E7F}' => Block::Thai,
+        c if is_latin(c) => Block::Latin,
+        _ => Block::Other,
+    };
+    Some(block)
+}
+
+/// Checks whether a character is ASCII or belongs to one of the Latin blocks, counting general
+/// punctuation and spaces as Latin as well.
+pub(crate) fn is_latin(c: char) -> bool {
+    matches!(c, '\u{0}'..='\u{24F}' | '\u{1E00}'..='\u{1EFF}' | '\u{2000}'..='\u{206F}')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_detect_the_main_scripts() {
+        assert_eq!(sentence_script("今日は天気です。"), Some(Script::Japanese));
+        assert_eq!(sentence_script("这是我们的学校"), Some(Script::ChineseSimplified));
+        assert_eq!(sentence_script("這是我們的學校"), Some(Script::ChineseTraditional));
+        assert_eq!(sentence_script("วันนี้อากาศดี"), Some(Script::Thai));
+        assert_eq!(sentence_script("Hello, world!"), Some(Script::Latin));
+        assert_eq!(sentence_script("Привет"), Some(Script::Unknown));
+        assert_eq!(sentence_script("abc学校ไทย"), Some(Script::Mixed));
+    }
+
+    #[test]
+    fn should_return_none_without_letters() {
+        assert_eq!(sentence_script(""), None);
+        assert_eq!(sentence_script("123 !?"), None);
+    }
+}
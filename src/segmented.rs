@@ -0,0 +1,26 @@
+//! The segmented text format of the upstream BudouX training data, shared by `Corpus` and
+//! `ModelTrainer`.
+
+/// The separator marking chunk boundaries in segmented text.
+pub(crate) const SEP: char = '▁';
+
+/// Splits segmented text such as `今日は▁天気です。` into the plain sentence and the boundary
+/// positions in characters. Repeated separators and separators at either end are ignored.
+pub(crate) fn parse_segmented(segmented: &str) -> (String, Vec<usize>) {
+    let mut sentence = String::with_capacity(segmented.len());
+    let mut boundaries = Vec::new();
+    let mut position = 0;
+    for c in segmented.chars() {
+        if c != SEP {
+            sentence.push(c);
+            position += 1;
+        } else if position > 0 && boundaries.last() != Some(&position) {
+            boundaries.push(position);
+        }
+    }
+    if boundaries.last() == Some(&position) {
+        boundaries.pop();
+    }
+
+    (sentence, boundaries)
+}
@@ -0,0 +1,221 @@
+use crate::Parser;
+use crate::parser::split_at_boundaries;
+
+/// Punctuation marks after which `RuleBasedSegmenter` breaks, in addition to whitespace.
+const RULE_BREAKS: &[char] = &['、', '。', '，', '．', '！', '？', '；', '：', '…'];
+
+/// A common interface for splitting text into chunks.
+///
+/// Implementors only need to provide `segment`; `segment_boundaries` is derived from it by
+/// default, but can be overridden when boundaries are cheaper to compute directly.
+pub trait Segmenter {
+    /// Splits the input sentence into chunks.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The chunks, which concatenate back to the input sentence unless the segmenter drops
+    /// characters.
+    fn segment<'a>(&self, s: &'a str) -> Vec<&'a str>;
+
+    /// Splits the input sentence and returns the chunk boundaries.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - An input sentence.
+    ///
+    /// # Returns
+    ///
+    /// The list of boundary positions in characters, counting the characters of the chunks.
+    fn segment_boundaries(&self, s: &str) -> Vec<usize> {
+        let chunks = self.segment(s);
+        let mut result = Vec::new();
+        let mut position = 0;
+
+        for chunk in chunks.iter().take(chunks.len().saturating_sub(1)) {
+            position += chunk.chars().count();
+            result.push(position);
+        }
+
+        result
+    }
+}
+
+/// Segments like `parse`, honoring `with_zero_width_space_as_break` and `with_bom_strip`: the
+/// dropped characters are not part of the chunks, and the boundaries do not count them.
+impl Segmenter for Parser<'_> {
+    fn segment<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        self.parse(s)
+    }
+
+    fn segment_boundaries(&self, s: &str) -> Vec<usize> {
+        self.char_boundaries(s)
+    }
+}
+
+/// A segmenter that breaks after whitespace and full-width punctuation without using a model.
+///
+/// Consecutive break characters are kept together in the preceding chunk.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RuleBasedSegmenter;
+
+impl Segmenter for RuleBasedSegmenter {
+    fn segment<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        if s.is_empty() {
+            return Vec::new();
+        }
+
+        split_at_boundaries(s, &self.segment_boundaries(s))
+    }
+
+    fn segment_boundaries(&self, s: &str) -> Vec<usize> {
+        let is_break = |c: char| c.is_whitespace() || RULE_BREAKS.contains(&c);
+        let chars = s.chars().collect::<Vec<_>>();
+
+        (1..chars.len())
+            .filter(|&i| is_break(chars[i - 1]) && !is_break(chars[i]))
+            .collect()
+    }
+}
+
+/// A segmenter that uses `secondary` whenever `primary` does not split the input at all.
+///
+/// This is typically used to pair a `Parser` with a `RuleBasedSegmenter`, so that text the
+/// model has no opinion on is still broken at whitespace and punctuation.
+#[derive(Debug, Clone)]
+pub struct Fallback<P, S> {
+    /// Segmenter tried first
+    primary: P,
+    /// Segmenter used when the primary one yields a single chunk
+    secondary: S,
+}
+
+impl<P, S> Fallback<P, S> {
+    /// Constructs a fallback segmenter.
+    ///
+    /// # Arguments
+    ///
+    /// * `primary` - The segmenter tried first.
+    /// * `secondary` - The segmenter used when `primary` yields a single chunk.
+    pub fn new(primary: P, secondary: S) -> Self {
+        Fallback { primary, secondary }
+    }
+}
+
+impl<P: Segmenter, S: Segmenter> Segmenter for Fallback<P, S> {
+    fn segment<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        let chunks = self.primary.segment(s);
+        if chunks.len() > 1 {
+            chunks
+        } else {
+            self.secondary.segment(s)
+        }
+    }
+
+    fn segment_boundaries(&self, s: &str) -> Vec<usize> {
+        let boundaries = self.primary.segment_boundaries(s);
+        if boundaries.is_empty() {
+            self.secondary.segment_boundaries(s)
+        } else {
+            boundaries
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_from_tuples;
+    use crate::models::Model;
+
+    struct EveryTwo;
+
+    impl Segmenter for EveryTwo {
+        fn segment<'a>(&self, s: &'a str) -> Vec<&'a str> {
+            let mut result = Vec::new();
+            let mut rest = s;
+            while !rest.is_empty() {
+                let end = rest.char_indices().nth(2).map(|(i, _)| i).unwrap_or(rest.len());
+                result.push(&rest[..end]);
+                rest = &rest[end..];
+            }
+            result
+        }
+    }
+
+    #[test]
+    fn should_derive_boundaries_from_segments() {
+        assert_eq!(EveryTwo.segment_boundaries("あいうえお"), vec![2, 4]);
+        assert_eq!(EveryTwo.segment_boundaries(""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn should_agree_with_parser_boundaries() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+
+        let segmenter: &dyn Segmenter = &Parser::new(model);
+
+        assert_eq!(segmenter.segment("abcdeabcd"), vec!["a", "bcdea", "bcd"]);
+        assert_eq!(segmenter.segment_boundaries("abcdeabcd"), vec![1, 6]);
+    }
+
+    /// Segments with a parser, leaving `segment_boundaries` to the default derivation.
+    struct SegmentOnly<'a>(&'a Parser<'a>);
+
+    impl Segmenter for SegmentOnly<'_> {
+        fn segment<'a>(&self, s: &'a str) -> Vec<&'a str> {
+            self.0.segment(s)
+        }
+    }
+
+    #[test]
+    fn should_agree_with_segment_when_zero_width_spaces_are_dropped() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model).with_zero_width_space_as_break(true);
+
+        for sentence in ["aa\u{200B}aa", "\u{200B}ab\u{200B}ab", "\u{200B}"] {
+            assert_eq!(
+                parser.segment_boundaries(sentence),
+                SegmentOnly(&parser).segment_boundaries(sentence),
+                "{sentence:?}"
+            );
+        }
+        assert_eq!(parser.segment_boundaries("aa\u{200B}aa"), vec![2]);
+    }
+
+    #[test]
+    fn should_agree_with_segment_when_a_byte_order_mark_is_stripped() {
+        let model = model_from_tuples! { "UW4" => [("b", 10000)] };
+        let parser = Parser::new(model).with_bom_strip(true);
+
+        for sentence in ["\u{FEFF}abab", "\u{FEFF}", "ab\u{FEFF}ab"] {
+            assert_eq!(
+                parser.segment_boundaries(sentence),
+                SegmentOnly(&parser).segment_boundaries(sentence),
+                "{sentence:?}"
+            );
+        }
+        assert_eq!(parser.segment_boundaries("\u{FEFF}abab"), vec![1, 3]);
+    }
+
+    #[test]
+    fn should_split_after_whitespace_and_punctuation() {
+        assert_eq!(
+            RuleBasedSegmenter.segment("今日は、晴れ。  明日は？！雨"),
+            vec!["今日は、", "晴れ。  ", "明日は？！", "雨"]
+        );
+        assert_eq!(RuleBasedSegmenter.segment("。"), vec!["。"]);
+    }
+
+    #[test]
+    fn should_fall_back_when_the_model_does_not_split() {
+        let parser = Parser::new(Model::new());
+        let segmenter = Fallback::new(parser, RuleBasedSegmenter);
+
+        assert_eq!(segmenter.segment("あい。うえ"), vec!["あい。", "うえ"]);
+        assert_eq!(segmenter.segment_boundaries("あい。うえ"), vec![3]);
+    }
+}
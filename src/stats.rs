@@ -0,0 +1,63 @@
+/// Summary metrics of a single parse, as returned by `Parser::parse_with_stats`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ParseStats {
+    /// Number of characters in the sentence
+    pub chars: usize,
+    /// Number of chunks produced
+    pub chunks: usize,
+    /// Length of the longest chunk in characters
+    pub max_chunk_chars: usize,
+    /// Average chunk length in characters
+    pub mean_chunk_chars: f64,
+}
+
+impl ParseStats {
+    /// Computes the metrics of a parse result.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunks` - The chunks returned by the parser.
+    pub(crate) fn from_chunks(chunks: &[&str]) -> Self {
+        let lengths = chunks.iter().map(|chunk| chunk.chars().count()).collect::<Vec<_>>();
+        let chars = lengths.iter().sum::<usize>();
+
+        ParseStats {
+            chars,
+            chunks: chunks.len(),
+            max_chunk_chars: lengths.iter().copied().max().unwrap_or(0),
+            mean_chunk_chars: if chunks.is_empty() {
+                0.0
+            } else {
+                chars as f64 / chunks.len() as f64
+            },
+        }
+    }
+
+    /// Serializes the metrics as a single-line JSON object, for shipping to logging and metrics
+    /// systems.
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"chars":{},"chunks":{},"max_chunk_chars":{},"mean_chunk_chars":{}}}"#,
+            self.chars, self.chunks, self.max_chunk_chars, self.mean_chunk_chars
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_serialize_stats_as_json() {
+        let stats = ParseStats::from_chunks(&["a", "bcdea", "bcd"]);
+
+        assert_eq!(
+            stats.to_json(),
+            r#"{"chars":9,"chunks":3,"max_chunk_chars":5,"mean_chunk_chars":3}"#
+        );
+        assert_eq!(
+            ParseStats::default().to_json(),
+            r#"{"chars":0,"chunks":0,"max_chunk_chars":0,"mean_chunk_chars":0}"#
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::parser::{LOOKAHEAD, LOOKBEHIND};
+use crate::{Parser, ScoringContext};
+use std::collections::VecDeque;
+
+/// A parser for text arriving in pieces, e.g. from a network socket, that emits every chunk as
+/// soon as the text following it can no longer move its boundaries.
+///
+/// A boundary depends on up to three characters after it, so the positions among the last two
+/// characters fed are only scored once more text arrives or the stream is flushed. Every position
+/// is scored once, so the cost is linear in the length of the stream however it is split. The
+/// chunks end at the same boundaries as `Parser::parse_char_boundaries` finds in the whole text,
+/// unless the parser has feature extractors, which only see the three characters on either side
+/// of the scored position. Created by `Parser::parse_chunked_stream`.
+pub struct StreamParser<'a> {
+    /// Parser used for scoring
+    parser: &'a Parser<'a>,
+    /// Characters from up to `LOOKBEHIND` before the next position to score up to the end of
+    /// the text fed so far
+    window: VecDeque<char>,
+    /// Number of characters of `window` before the next position to score
+    before: usize,
+    /// Whether the next position to score is the start of the stream, which is never a boundary
+    at_start: bool,
+    /// Text of the unfinished chunks
+    pending: String,
+    /// Length in bytes of the text of `pending` before the next position to score
+    scored: usize,
+}
+
+impl<'a> StreamParser<'a> {
+    /// Constructs a stream parser without buffered text.
+    ///
+    /// # Arguments
+    ///
+    /// * `parser` - The parser scoring the stream.
+    pub fn new(parser: &'a Parser<'a>) -> Self {
+        StreamParser {
+            parser,
+            window: VecDeque::new(),
+            before: 0,
+            at_start: true,
+            pending: String::new(),
+            scored: 0,
+        }
+    }
+
+    /// Appends a piece of the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The next piece of the stream.
+    ///
+    /// # Returns
+    ///
+    /// The chunks completed by the piece, possibly none.
+    #[must_use]
+    pub fn feed(&mut self, text: &str) -> Vec<String> {
+        self.pending.push_str(text);
+        self.window.extend(text.chars());
+        self.take_chunks(false)
+    }
+
+    /// Ends the stream, emitting the held back text. The parser can then start a new stream.
+    ///
+    /// # Returns
+    ///
+    /// The remaining chunks, none if no text is held back.
+    #[must_use]
+    pub fn flush(&mut self) -> Vec<String> {
+        let mut chunks = self.take_chunks(true);
+        if !self.pending.is_empty() {
+            chunks.push(std::mem::take(&mut self.pending));
+        }
+        self.window.clear();
+        self.before = 0;
+        self.at_start = true;
+        self.scored = 0;
+        chunks
+    }
+
+    /// Scores the positions whose boundary is known, all of them if the stream has ended and only
+    /// those with enough text after them otherwise, and splits off the chunks they end.
+    fn take_chunks(&mut self, end: bool) -> Vec<String> {
+        let mut chunks = Vec::new();
+        let mut text = String::with_capacity(LOOKBEHIND + LOOKAHEAD);
+
+        while let Some(&next) = self.window.get(self.before) {
+            if !self.at_start {
+                let after = self.window.len() - self.before;
+                if after < LOOKAHEAD && !end {
+                    break;
+                }
+
+                text.clear();
+                text.extend(self.window.range(..(self.before + LOOKAHEAD).min(self.window.len())));
+                let score = self.parser.score_in_context(&ScoringContext::new(&text), self.before);
+                if self.parser.is_boundary_score(score) {
+                    let rest = self.pending.split_off(self.scored);
+                    chunks.push(std::mem::replace(&mut self.pending, rest));
+                    self.scored = 0;
+                }
+            }
+
+            self.at_start = false;
+            self.scored += next.len_utf8();
+            if self.before == LOOKBEHIND {
+                self.window.pop_front();
+            } else {
+                self.before += 1;
+            }
+        }
+
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_the_boundaries_of_the_whole_text() {
+        let parser = Parser::new(crate::model_from_tuples! {
+            "UW1" => [("c", 10000)],
+            "UW6" => [("c", 10000)],
+            "TW4" => [("bab", 10000)],
+            "UW2" => [("x", -30000)],
+        });
+        let text = "acbaあbbabaabbcaacあb";
+        let expected = split_at(text, &parser.char_boundaries(text));
+
+        for k in (0..=text.len()).filter(|&k| text.is_char_boundary(k)) {
+            let mut stream = parser.parse_chunked_stream();
+            let mut chunks = stream.feed(&text[..k]);
+            chunks.extend(stream.feed(&text[k..]));
+            chunks.extend(stream.flush());
+            assert_eq!(chunks, expected, "split at {}", k);
+        }
+
+        let mut stream = parser.parse_chunked_stream();
+        let mut chunks = Vec::new();
+        for c in text.chars() {
+            chunks.extend(stream.feed(&c.to_string()));
+        }
+        assert_eq!(chunks.len(), expected.len() - 1);
+        chunks.extend(stream.flush());
+        assert_eq!(chunks, expected);
+        assert!(stream.flush().is_empty());
+    }
+
+    fn split_at(text: &str, boundaries: &[usize]) -> Vec<String> {
+        crate::parser::split_at_boundaries(text, boundaries)
+            .into_iter()
+            .map(str::to_string)
+            .collect()
+    }
+}
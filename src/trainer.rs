@@ -0,0 +1,235 @@
+use crate::models::Model;
+use crate::parser::FEATURE_WINDOWS;
+use crate::segmented::parse_segmented;
+use std::{collections::HashMap, fs, io, path::Path};
+
+/// Factor applied to the learned feature weights when converting them to integer scores.
+const SCALE: f64 = 1000.0;
+
+/// Number of boosting rounds used by `ModelTrainer::train` unless configured otherwise.
+const DEFAULT_ITERATIONS: usize = 1000;
+
+/// A trainer that learns a BudouX model from sentences with known boundaries.
+///
+/// Training follows the upstream BudouX script: AdaBoost over the presence of each model
+/// feature, where the weight of a feature is the sum of the coefficients it was selected with.
+/// A position is a boundary when the weights of its features exceed half of all weights, which
+/// is exactly the decision made by `Parser`.
+#[derive(Debug, Clone)]
+pub struct ModelTrainer {
+    /// Features of each position (indices into `features`) and whether it is a boundary
+    samples: Vec<(Vec<usize>, bool)>,
+    /// Distinct features seen so far, as `(group, substring)` pairs
+    features: Vec<(&'static str, String)>,
+    /// Index of each feature in `features`
+    index: HashMap<(&'static str, String), usize>,
+    /// Number of boosting rounds
+    iterations: usize,
+}
+
+impl Default for ModelTrainer {
+    fn default() -> Self {
+        ModelTrainer {
+            samples: Vec::new(),
+            features: Vec::new(),
+            index: HashMap::new(),
+            iterations: DEFAULT_ITERATIONS,
+        }
+    }
+}
+
+impl ModelTrainer {
+    /// Constructs an empty trainer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a trainer from a corpus file.
+    ///
+    /// Each line of the file is a training sentence with its chunks separated by `▁` (U+2581),
+    /// as in the upstream training data and the expected segmentations of `Corpus`, e.g.
+    /// `今日は▁天気です。`. Empty lines are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to a corpus file.
+    ///
+    /// # Returns
+    ///
+    /// The trainer holding every sentence of the file, or an error if it cannot be read.
+    pub fn from_corpus_file(path: &Path) -> Result<ModelTrainer, io::Error> {
+        let mut trainer = Self::new();
+
+        for line in fs::read_to_string(path)?.lines() {
+            let (sentence, boundaries) = parse_segmented(line);
+            if !sentence.is_empty() {
+                trainer.add(&sentence, &boundaries);
+            }
+        }
+
+        Ok(trainer)
+    }
+
+    /// Sets the number of boosting rounds, which bounds the number of entries in the model.
+    ///
+    /// # Arguments
+    ///
+    /// * `iterations` - The number of rounds.
+    ///
+    /// # Returns
+    ///
+    /// The trainer with the new number of rounds.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Adds a training sentence.
+    ///
+    /// # Arguments
+    ///
+    /// * `sentence` - A training sentence.
+    /// * `boundaries` - The expected boundary positions in characters.
+    pub fn add(&mut self, sentence: &str, boundaries: &[usize]) {
+        let chars = sentence.chars().collect::<Vec<_>>();
+        let len = chars.len();
+
+        for i in 1..len {
+            let mut features = Vec::new();
+            for (key, start, end) in FEATURE_WINDOWS {
+                let start = i.saturating_add_signed(start).min(len);
+                let end = i.saturating_add_signed(end).min(len);
+                if start >= end {
+                    continue;
+                }
+
+                let feature = (key, chars[start..end].iter().collect::<String>());
+                let next = self.features.len();
+                let id = *self.index.entry(feature.clone()).or_insert(next);
+                if id == next {
+                    self.features.push(feature);
+                }
+                features.push(id);
+            }
+
+            self.samples.push((features, boundaries.contains(&i)));
+        }
+    }
+
+    /// Returns the number of training positions collected so far.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Checks whether no training positions have been collected.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Trains a model on the collected sentences.
+    ///
+    /// # Returns
+    ///
+    /// The trained model, empty if no training positions were collected.
+    pub fn train(&self) -> Model {
+        if self.samples.is_empty() {
+            return Model::new();
+        }
+
+        let n = self.samples.len();
+        let mut weights = vec![1.0 / n as f64; n];
+        let mut alphas = vec![0.0; self.features.len()];
+
+        for _ in 0..self.iterations {
+            let positive = self
+                .samples
+                .iter()
+                .zip(&weights)
+                .filter(|((_, y), _)| *y)
+                .map(|(_, w)| w)
+                .sum::<f64>();
+
+            // The error of a feature used as a classifier (present: boundary, absent: no
+            // boundary) is the weight of positives without it plus negatives with it.
+            let mut sums = vec![0.0; self.features.len()];
+            for ((features, y), w) in self.samples.iter().zip(&weights) {
+                for &f in features {
+                    sums[f] += if *y { *w } else { -*w };
+                }
+            }
+            let Some((best, error)) = sums
+                .iter()
+                .map(|sum| positive - sum)
+                .enumerate()
+                .max_by(|(_, a), (_, b)| (0.5 - a).abs().total_cmp(&(0.5 - b).abs()))
+            else {
+                break;
+            };
+            if (0.5 - error).abs() < f64::EPSILON {
+                break;
+            }
+
+            let error = error.clamp(1e-10, 1.0 - 1e-10);
+            let alpha = 0.5 * ((1.0 - error) / error).ln();
+            alphas[best] += alpha;
+
+            for ((features, y), w) in self.samples.iter().zip(weights.iter_mut()) {
+                let h = if features.contains(&best) { 1.0 } else { -1.0 };
+                let y = if *y { 1.0 } else { -1.0 };
+                *w *= (-alpha * y * h).exp();
+            }
+            let total = weights.iter().sum::<f64>();
+            weights.iter_mut().for_each(|w| *w /= total);
+        }
+
+        let mut model = Model::new();
+        for ((key, subkey), alpha) in self.features.iter().zip(alphas) {
+            let score = (alpha * SCALE).round() as i64;
+            if score != 0 {
+                model.entry(key.to_string()).or_default().insert(subkey.clone(), score);
+            }
+        }
+        model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    #[test]
+    fn should_learn_boundaries_of_the_training_data() {
+        let mut trainer = ModelTrainer::new().with_iterations(50);
+        trainer.add("abcabc", &[3]);
+        trainer.add("xyabc", &[2]);
+        trainer.add("abcxy", &[3]);
+
+        let parser = Parser::new(trainer.train());
+
+        assert_eq!(trainer.len(), 13);
+        assert_eq!(parser.parse("abcabc"), vec!["abc", "abc"]);
+        assert_eq!(parser.parse("xyabc"), vec!["xy", "abc"]);
+    }
+
+    #[test]
+    fn should_train_an_empty_model_without_data() {
+        assert!(ModelTrainer::new().train().is_empty());
+    }
+
+    #[test]
+    #[cfg_attr(target_os = "wasi", ignore = "no filesystem")]
+    fn should_load_segmented_sentences_from_a_file() {
+        let path = std::env::temp_dir().join(format!("budoux-trainer-{}.txt", std::process::id()));
+        fs::write(&path, "abc▁abc\n\nxy▁abc\n").unwrap();
+
+        let trainer = ModelTrainer::from_corpus_file(&path);
+        fs::remove_file(&path).unwrap();
+
+        let trainer = trainer.unwrap().with_iterations(50);
+        let parser = Parser::new(trainer.train());
+
+        assert_eq!(trainer.len(), 9);
+        assert_eq!(parser.parse("abcabc"), vec!["abc", "abc"]);
+    }
+}
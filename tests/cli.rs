@@ -0,0 +1,116 @@
+//! End-to-end tests running the `cli` example on the fixtures in `tests/fixtures/cli`.
+//!
+//! Each language has an input sentence in `<lang>.in.txt` and the expected chunks, one per line,
+//! in `<lang>.out.txt`. The example needs a language model, so nothing runs without one, and
+//! processes cannot be spawned on WASI.
+
+#![cfg(all(
+    not(target_os = "wasi"),
+    any(feature = "ja", feature = "zh-hans", feature = "zh-hant", feature = "th")
+))]
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+};
+
+/// The language features enabled for this test run, passed on to the example build.
+fn enabled_languages() -> Vec<&'static str> {
+    [
+        ("ja", cfg!(feature = "ja")),
+        ("zh-hans", cfg!(feature = "zh-hans")),
+        ("zh-hant", cfg!(feature = "zh-hant")),
+        ("th", cfg!(feature = "th")),
+    ]
+    .into_iter()
+    .filter(|&(_, enabled)| enabled)
+    .map(|(lang, _)| lang)
+    .collect()
+}
+
+/// Returns the path of the `cli` example. `cargo test` builds it next to the test binaries with
+/// the same features; if it is missing, e.g. with `cargo test --test cli`, it is built once in a
+/// separate target directory, so that the build does not wait on the lock held by the test run.
+fn cli_path() -> &'static Path {
+    static PATH: OnceLock<PathBuf> = OnceLock::new();
+
+    PATH.get_or_init(|| {
+        let name = format!("cli{}", env::consts::EXE_SUFFIX);
+        let test_exe = env::current_exe().expect("failed to locate the test binary");
+        // The test binary is in `<profile>/deps`, and the examples in `<profile>/examples`.
+        let profile_dir = test_exe.parent().and_then(Path::parent).unwrap();
+        let built = profile_dir.join("examples").join(&name);
+        if built.exists() {
+            return built;
+        }
+
+        let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("cli");
+        let status = Command::new(env!("CARGO"))
+            .args(["build", "--quiet", "--example", "cli", "--features"])
+            .arg(enabled_languages().join(","))
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .status()
+            .expect("failed to run cargo");
+        assert!(status.success(), "failed to build the cli example");
+        target_dir.join("debug").join("examples").join(name)
+    })
+}
+
+/// Runs the `cli` example with the given arguments.
+fn run_cli(args: &[&str]) -> std::process::Output {
+    Command::new(cli_path())
+        .args(args)
+        .output()
+        .expect("failed to run the cli example")
+}
+
+/// Checks that the CLI splits the fixture sentence of a language into the expected chunks.
+fn check_fixture(lang: &str) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("cli");
+    let input = fs::read_to_string(fixtures.join(format!("{}.in.txt", lang))).unwrap();
+    let expected = fs::read_to_string(fixtures.join(format!("{}.out.txt", lang))).unwrap();
+
+    let output = run_cli(&["--lang", lang, input.trim()]);
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), expected.lines().collect::<Vec<_>>());
+}
+
+#[test]
+#[cfg(feature = "ja")]
+fn should_chunk_japanese() {
+    check_fixture("ja");
+}
+
+#[test]
+#[cfg(feature = "zh-hans")]
+fn should_chunk_simplified_chinese() {
+    check_fixture("zh-hans");
+}
+
+#[test]
+#[cfg(feature = "zh-hant")]
+fn should_chunk_traditional_chinese() {
+    check_fixture("zh-hant");
+}
+
+#[test]
+#[cfg(feature = "th")]
+fn should_chunk_thai() {
+    check_fixture("th");
+}
+
+#[test]
+fn should_reject_unknown_languages() {
+    let output = run_cli(&["--lang", "xx", "text"]);
+
+    assert!(!output.status.success());
+}